@@ -1,39 +1,106 @@
+use crate::validator::Validator;
+
 use bitflags::bitflags;
-use syn::{Attribute, Expr, Meta, NestedMeta};
+use proc_macro2::Ident;
+use syn::{
+    ext::IdentExt, parenthesized, parse::ParseStream, parse_quote, punctuated::Punctuated, token,
+    Attribute, Expr, ExprClosure, LitStr, Pat, Token, Type, Visibility,
+};
 
 bitflags! {
     pub struct Setters: u32 {
         const VALUE = 0b00000001;
         const LAZY = 0b00000010;
         const ASYNC = 0b00000100;
+        /// Repeatable setters adding items to the collection.
+        const EACH = 0b00001000;
+        /// Setters of `bool` fields taking no arguments.
+        const FLAG = 0b00010000;
     }
 }
 
-#[derive(PartialEq)]
+/// Fallible conversions of values given to setters.
+#[derive(Clone, Copy)]
+pub enum Conversion {
+    /// Setters take `TryInto<T>`.
+    TryInto,
+    /// Setters take `&str` and parse it via `FromStr`.
+    Parse,
+}
+
 pub enum FieldVisibility {
     Public,
     Hidden,
     Default,
+    /// Visibility given via `#[setter(vis = "...")]`.
+    Custom(Visibility),
 }
 
 pub struct FieldAttributes {
     pub default: Option<(Expr, Setters)>,
+    /// The lazy default takes the context of the builder.
+    pub default_with_context: bool,
     pub use_into: bool,
-    pub validator: Option<Expr>,
+    pub validator: Option<Validator>,
+    pub validator_async: Option<Expr>,
+    /// A validator taking the context of the builder, evaluated when the structure is built.
+    pub validator_context: Option<Expr>,
+    /// Functions applied to the value before validation.
+    /// The flag means it is also applied to the default value.
+    pub transforms: Vec<(Expr, bool)>,
     pub documents: Vec<Attribute>,
     pub setters: Setters,
     pub vis: FieldVisibility,
+    /// Setters take `T` for the field of `Option<T>`.
+    pub strip_option: bool,
+    /// Name of the setter adding an item to the collection.
+    pub each: Option<Ident>,
+    pub conversion: Option<Conversion>,
+    /// Setters wrap values with the smart pointer of the field.
+    pub wrap: bool,
+    /// Setters take closures for the field of a boxed function.
+    pub closure: bool,
+    /// Setters with names, taking arguments of the closures which make the value.
+    pub custom_setters: Vec<(Ident, ExprClosure)>,
+    /// Setters are provided even if the field is already set.
+    pub overridable: bool,
+    /// The builder has functions to check the state of the field and peek the value.
+    pub getter: bool,
+    /// The builder has a function to transform the value of the field.
+    pub map: bool,
+    /// The field is set by presets, so its state may be `Preset<T>`.
+    pub preset: bool,
+    /// A name used instead of the field name in names of the functions.
+    pub rename: Option<String>,
+    /// The field has no default values even if they are given implicitly.
+    pub required: bool,
 }
 
 impl Default for FieldAttributes {
     fn default() -> Self {
         FieldAttributes {
             default: None,
+            default_with_context: false,
             use_into: false,
             validator: None,
+            validator_async: None,
+            validator_context: None,
+            transforms: vec![],
             documents: vec![],
-            setters: Setters::VALUE,
+            setters: Setters::empty(),
             vis: FieldVisibility::Default,
+            strip_option: false,
+            each: None,
+            conversion: None,
+            wrap: false,
+            closure: false,
+            custom_setters: vec![],
+            overridable: false,
+            getter: false,
+            map: false,
+            preset: false,
+            rename: None,
+            required: false,
         }
     }
 }
@@ -58,25 +125,40 @@ impl From<Vec<Attribute>> for FieldAttributes {
                 }
                 unimplemented!("Asynchronous default is not implemented yet.")
             } else if attr.path.is_ident("hidden") {
-                if attributes.vis != FieldVisibility::Default {
+                if !matches!(attributes.vis, FieldVisibility::Default) {
                     unimplemented!("Duplicated `hidden` attributes.")
                 }
                 attributes.vis = FieldVisibility::Hidden;
             } else if attr.path.is_ident("public") {
-                if attributes.vis != FieldVisibility::Default {
+                if !matches!(attributes.vis, FieldVisibility::Default) {
                     unimplemented!("Duplicated `public` attributes.")
                 }
                 attributes.vis = FieldVisibility::Public;
+            } else if attr.path.is_ident("required") {
+                attributes.required = true;
             } else if attr.path.is_ident("into") {
                 attributes.use_into = true
             } else if attr.path.is_ident("validator") {
                 parse_validator(attr, &mut attributes)
+            } else if attr.path.is_ident("validator_async") {
+                parse_async_validator(attr, &mut attributes)
+            } else if attr.path.is_ident("transform") {
+                parse_transform(attr, &mut attributes)
             } else if attr.path.is_ident("doc") {
                 attributes.documents = get_documents(&attrs);
             } else if attr.path.is_ident("setter") {
                 parse_setters(attr, &mut attributes)
             }
         });
+        // Only the value setter is provided if no kinds of setters are given.
+        // Fields having `each` setters don't have it unless it is given explicitly,
+        // because it would replace the items added before.
+        if (attributes.setters & (Setters::VALUE | Setters::LAZY | Setters::ASYNC | Setters::FLAG))
+            .is_empty()
+            && attributes.each.is_none()
+        {
+            attributes.setters.insert(Setters::VALUE);
+        }
         match attributes.validate() {
             Ok(_) => attributes,
             Err(e) => unimplemented!("{}", e),
@@ -85,6 +167,11 @@ impl From<Vec<Attribute>> for FieldAttributes {
 }
 
 fn parse_default(attr: &Attribute, attributes: &mut FieldAttributes) {
+    // `#[default]` without an argument means `Default::default()`.
+    if attr.tokens.is_empty() {
+        attributes.default = Some((parse_quote!(Default::default()), Setters::VALUE));
+        return;
+    }
     attributes.default = match attr.parse_args() {
         Ok(ex) => Some((ex, Setters::VALUE)),
         Err(_) => unimplemented!("Invalid default value."),
@@ -92,41 +179,448 @@ fn parse_default(attr: &Attribute, attributes: &mut FieldAttributes) {
 }
 
 fn parse_lazy_default(attr: &Attribute, attributes: &mut FieldAttributes) {
+    if let Ok(ex) = attr.parse_args_with(parse_with_context) {
+        attributes.default = Some((ex, Setters::LAZY));
+        attributes.default_with_context = true;
+        return;
+    }
     attributes.default = match attr.parse_args() {
+        // A closure having an argument takes the context.
+        Ok(Expr::Closure(c)) if c.inputs.len() == 1 => {
+            attributes.default_with_context = true;
+            Some((Expr::Closure(c), Setters::LAZY))
+        }
         Ok(ex) => Some((ex, Setters::LAZY)),
         Err(_) => unimplemented!("Invalid default value."),
     };
 }
 
 fn parse_validator(attr: &Attribute, attributes: &mut FieldAttributes) {
+    if let Ok(ex) = attr.parse_args_with(parse_with_context) {
+        attributes.validator_context = Some(ex);
+        return;
+    }
     attributes.validator = match attr.parse_args() {
         Ok(ex) => Some(ex),
         Err(_) => unimplemented!("Invalid validator."),
     };
 }
 
+/// Parse `expr, with_context`.
+fn parse_with_context(input: ParseStream) -> syn::Result<Expr> {
+    let expr = input.parse()?;
+    input.parse::<Token![,]>()?;
+    let flag: Ident = input.parse()?;
+    if flag != "with_context" || !input.is_empty() {
+        return Err(input.error("expected `with_context`"));
+    }
+    Ok(expr)
+}
+
+fn parse_async_validator(attr: &Attribute, attributes: &mut FieldAttributes) {
+    attributes.validator_async = match attr.parse_args() {
+        Ok(ex) => Some(ex),
+        Err(_) => unimplemented!("Invalid validator."),
+    };
+}
+
+fn parse_transform(attr: &Attribute, attributes: &mut FieldAttributes) {
+    let transform = attr.parse_args_with(|input: ParseStream| {
+        let expr: Expr = input.parse()?;
+        let mut default = false;
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            parse_keyed_args(input, |key, input| {
+                if key == "default" {
+                    default = true;
+                    Ok(())
+                } else {
+                    Err(input.error(format!("unknown option `{}`", key)))
+                }
+            })?;
+        }
+        Ok((expr, default))
+    });
+    match transform {
+        Ok(t) => attributes.transforms.push(t),
+        Err(_) => unimplemented!("Invalid transform."),
+    }
+}
+
 fn parse_setters(attr: &Attribute, attributes: &mut FieldAttributes) {
-    let meta = attr.parse_meta().unwrap();
     let mut setters = Setters::empty();
-    if let Meta::List(l) = meta {
-        let it = l.nested.iter();
-        it.for_each(|m| {
-            if let NestedMeta::Meta(Meta::Path(p)) = m {
-                if p.is_ident("value") {
-                    setters.insert(Setters::VALUE);
-                } else if p.is_ident("lazy") {
-                    setters.insert(Setters::LAZY);
-                } else if p.is_ident("async") {
-                    setters.insert(Setters::ASYNC);
+    // A custom setter is given via `name` and `with`.
+    let mut name: Option<Ident> = None;
+    let mut with: Option<ExprClosure> = None;
+    let result = attr.parse_args_with(|input: ParseStream| {
+        parse_keyed_args(input, |key, input| {
+            if key == "name" {
+                input.parse::<Token![=]>()?;
+                name = Some(input.parse::<LitStr>()?.parse()?);
+                return Ok(());
+            } else if key == "with" {
+                input.parse::<Token![=]>()?;
+                with = Some(input.parse()?);
+                return Ok(());
+            }
+            if name.is_some() || with.is_some() {
+                return Err(input.error("custom setters can't have other options"));
+            }
+            if key == "value" {
+                setters.insert(Setters::VALUE);
+            } else if key == "lazy" {
+                setters.insert(Setters::LAZY);
+            } else if key == "async" {
+                setters.insert(Setters::ASYNC);
+            } else if key == "try_into" || key == "parse" {
+                if attributes.conversion.is_some() {
+                    unimplemented!("Duplicated conversions of the setter.")
+                }
+                attributes.conversion = Some(if key == "parse" {
+                    Conversion::Parse
+                } else {
+                    Conversion::TryInto
+                });
+            } else if key == "wrap" {
+                attributes.wrap = true;
+            } else if key == "closure" {
+                attributes.wrap = true;
+                attributes.closure = true;
+            } else if key == "flag" {
+                setters.insert(Setters::FLAG);
+            } else if key == "strip_option" {
+                attributes.strip_option = true;
+            } else if key == "overridable" {
+                attributes.overridable = true;
+            } else if key == "getter" {
+                attributes.getter = true;
+            } else if key == "map" {
+                attributes.map = true;
+            } else if key == "vis" {
+                if !matches!(attributes.vis, FieldVisibility::Default) {
+                    unimplemented!("Duplicated visibilities of the field.")
                 }
+                input.parse::<Token![=]>()?;
+                attributes.vis = FieldVisibility::Custom(input.parse::<LitStr>()?.parse()?);
+            } else if key == "rename" {
+                input.parse::<Token![=]>()?;
+                attributes.rename = Some(input.parse::<LitStr>()?.value());
+            } else if key == "each" {
+                input.parse::<Token![=]>()?;
+                let name: LitStr = input.parse()?;
+                attributes.each = Some(name.parse()?);
+                setters.insert(Setters::EACH);
             } else {
-                unimplemented!("Invalid setter.")
+                return Err(input.error(format!("unknown setter `{}`", key)));
             }
-        });
-    } else {
+            Ok(())
+        })
+    });
+    if result.is_err() {
         unimplemented!("Invalid setter.")
     }
-    attributes.setters = setters;
+    match (name, with) {
+        (Some(name), Some(with)) => {
+            if with
+                .inputs
+                .iter()
+                .any(|input| !matches!(input, Pat::Type(_)))
+            {
+                unimplemented!("Arguments of `with` should have their types.")
+            }
+            attributes.custom_setters.push((name, with));
+            return;
+        }
+        (None, None) => {}
+        _ => unimplemented!("Custom setters require `name` and `with`."),
+    }
+    // Kinds of setters given by several attributes are merged.
+    attributes.setters |= setters;
+}
+
+/// Attributes of the structure, given via `#[builder(...)]`.
+#[derive(Default)]
+pub struct StructAttributes {
+    pub validate: Option<Validator>,
+    pub finalize: Option<Expr>,
+    pub context: Option<Type>,
+    /// Error type of the build function. It is `&'static str` by default.
+    pub error: Option<Type>,
+    /// All fields of `Option<T>` have setters taking `T`.
+    pub strip_option: bool,
+    /// Setters of all fields are provided even if the fields are already set.
+    pub overridable: bool,
+    /// All fields have getters.
+    pub getters: bool,
+    /// All fields have functions transforming their values.
+    pub maps: bool,
+    pub setters: Vec<MultiSetter>,
+    /// Name of the builder.
+    pub name: Option<Ident>,
+    /// Name of the function creating the builder.
+    pub constructor: Option<Ident>,
+    /// Prefix of the names of setters.
+    pub prefix: Option<String>,
+    pub lazy_suffix: Option<String>,
+    pub async_suffix: Option<String>,
+    /// Visibility of the builder.
+    pub vis: Option<Visibility>,
+    pub build_fn: BuildFn,
+    /// Additional constructors setting fields from their arguments.
+    pub constructors: Vec<Constructor>,
+    pub presets: Vec<Preset>,
+    /// Fields are `Default::default()` by default unless they are `#[required]`.
+    pub default_all: bool,
+}
+
+/// A constructor creating the builder with fields set by the given values.
+/// The fields are still overridable.
+pub struct Preset {
+    pub name: Ident,
+    pub values: Vec<(Ident, Expr)>,
+}
+
+/// A constructor creating the builder with fields set by its arguments.
+pub struct Constructor {
+    pub name: Ident,
+    pub args: Vec<Ident>,
+}
+
+/// Options of the build function, given via `build_fn(...)`.
+#[derive(Default)]
+pub struct BuildFn {
+    pub name: Option<Ident>,
+    pub vis: Option<Visibility>,
+    /// `into_parts` is provided instead of the build function.
+    pub skip: bool,
+}
+
+/// A setter setting several fields at once.
+pub struct MultiSetter {
+    pub name: Ident,
+    pub fields: Vec<Ident>,
+    /// Setters of the fields are not provided.
+    pub replace: bool,
+}
+
+impl From<&[Attribute]> for StructAttributes {
+    fn from(attrs: &[Attribute]) -> StructAttributes {
+        let mut attributes = StructAttributes::default();
+        attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("builder"))
+            .for_each(|attr| {
+                if let Err(e) = attr.parse_args_with(|input: ParseStream| {
+                    parse_keyed_args(input, |key, input| {
+                        parse_builder_arg(key, input, &mut attributes)
+                    })
+                }) {
+                    unimplemented!("Invalid builder attribute: {}", e)
+                }
+            });
+        if attributes.build_fn.skip
+            && (attributes.validate.is_some() || attributes.finalize.is_some())
+        {
+            unimplemented!("`validate` and `finalize` can't be used with `build_fn(skip)`.")
+        }
+        attributes
+    }
+}
+
+fn parse_builder_arg(
+    key: &Ident,
+    input: ParseStream,
+    attributes: &mut StructAttributes,
+) -> syn::Result<()> {
+    if key == "validate" {
+        if attributes.validate.is_some() {
+            unimplemented!("Duplicated `validate` options.")
+        }
+        attributes.validate = if input.peek(token::Paren) {
+            // `validate(ref = expr)` and the other forms of field validators.
+            let content;
+            parenthesized!(content in input);
+            Some(content.parse()?)
+        } else {
+            input.parse::<Token![=]>()?;
+            Some(Validator::Function(input.parse()?))
+        };
+    } else if key == "finalize" {
+        if attributes.finalize.is_some() {
+            unimplemented!("Duplicated `finalize` options.")
+        }
+        input.parse::<Token![=]>()?;
+        attributes.finalize = Some(input.parse()?);
+    } else if key == "error" {
+        if attributes.error.is_some() {
+            unimplemented!("Duplicated `error` options.")
+        }
+        input.parse::<Token![=]>()?;
+        attributes.error = Some(input.parse()?);
+    } else if key == "context" {
+        if attributes.context.is_some() {
+            unimplemented!("Duplicated `context` options.")
+        }
+        input.parse::<Token![=]>()?;
+        attributes.context = Some(input.parse()?);
+    } else if key == "strip_option" {
+        attributes.strip_option = true;
+    } else if key == "overridable" {
+        attributes.overridable = true;
+    } else if key == "getters" {
+        attributes.getters = true;
+    } else if key == "maps" {
+        attributes.maps = true;
+    } else if key == "default_all" {
+        attributes.default_all = true;
+    } else if key == "constructor" && input.peek(token::Paren) {
+        let content;
+        parenthesized!(content in input);
+        attributes.constructors.push(parse_constructor(&content)?);
+    } else if key == "preset" {
+        let content;
+        parenthesized!(content in input);
+        attributes.presets.append(&mut parse_presets(&content)?);
+    } else if key == "name" || key == "constructor" {
+        input.parse::<Token![=]>()?;
+        let name = Some(input.parse::<LitStr>()?.parse()?);
+        if key == "name" {
+            attributes.name = name;
+        } else {
+            attributes.constructor = name;
+        }
+    } else if key == "prefix" || key == "lazy_suffix" || key == "async_suffix" {
+        input.parse::<Token![=]>()?;
+        let value = Some(input.parse::<LitStr>()?.value());
+        if key == "prefix" {
+            attributes.prefix = value;
+        } else if key == "lazy_suffix" {
+            attributes.lazy_suffix = value;
+        } else {
+            attributes.async_suffix = value;
+        }
+    } else if key == "vis" {
+        input.parse::<Token![=]>()?;
+        attributes.vis = Some(input.parse::<LitStr>()?.parse()?);
+    } else if key == "build_fn" {
+        let content;
+        parenthesized!(content in input);
+        parse_keyed_args(&content, |key, input| {
+            if key == "vis" {
+                input.parse::<Token![=]>()?;
+                attributes.build_fn.vis = Some(input.parse::<LitStr>()?.parse()?);
+            } else if key == "name" {
+                input.parse::<Token![=]>()?;
+                attributes.build_fn.name = Some(input.parse::<LitStr>()?.parse()?);
+            } else if key == "skip" {
+                attributes.build_fn.skip = true;
+            } else {
+                return Err(input.error(format!("unknown option `{}`", key)));
+            }
+            Ok(())
+        })?;
+    } else if key == "setter" {
+        let content;
+        parenthesized!(content in input);
+        attributes.setters.push(parse_multi_setter(&content)?);
+    } else {
+        return Err(input.error(format!("unknown option `{}`", key)));
+    }
+    Ok(())
+}
+
+fn parse_constructor(input: ParseStream) -> syn::Result<Constructor> {
+    let mut name: Option<Ident> = None;
+    let mut args = vec![];
+    parse_keyed_args(input, |key, input| {
+        if key == "name" {
+            input.parse::<Token![=]>()?;
+            name = Some(input.parse::<LitStr>()?.parse()?);
+        } else if key == "args" {
+            let content;
+            parenthesized!(content in input);
+            args = Punctuated::<Ident, Token![,]>::parse_terminated(&content)?
+                .into_iter()
+                .collect();
+        } else {
+            return Err(input.error(format!("unknown option `{}`", key)));
+        }
+        Ok(())
+    })?;
+    match name {
+        Some(name) => Ok(Constructor { name, args }),
+        None => Err(input.error("`constructor` requires `name`")),
+    }
+}
+
+/// Presets are given as `name(field = expr, ...)`.
+/// Names of presets are paths of the values, so Clippy doesn't report them as duplicated.
+fn parse_presets(input: ParseStream) -> syn::Result<Vec<Preset>> {
+    let mut presets = vec![];
+    parse_keyed_args(input, |name, input| {
+        let content;
+        parenthesized!(content in input);
+        let mut values = vec![];
+        parse_keyed_args(&content, |key, input| {
+            input.parse::<Token![=]>()?;
+            values.push((key.clone(), input.parse()?));
+            Ok(())
+        })?;
+        presets.push(Preset {
+            name: name.clone(),
+            values,
+        });
+        Ok(())
+    })?;
+    Ok(presets)
+}
+
+fn parse_multi_setter(input: ParseStream) -> syn::Result<MultiSetter> {
+    let mut name: Option<Ident> = None;
+    let mut fields = vec![];
+    let mut replace = false;
+    parse_keyed_args(input, |key, input| {
+        if key == "name" {
+            input.parse::<Token![=]>()?;
+            name = Some(input.parse::<LitStr>()?.parse()?);
+        } else if key == "fields" {
+            let content;
+            parenthesized!(content in input);
+            fields = Punctuated::<Ident, Token![,]>::parse_terminated(&content)?
+                .into_iter()
+                .collect();
+        } else if key == "replace" {
+            replace = true;
+        } else {
+            return Err(input.error(format!("unknown option `{}`", key)));
+        }
+        Ok(())
+    })?;
+    match name {
+        Some(name) if !fields.is_empty() => Ok(MultiSetter {
+            name,
+            fields,
+            replace,
+        }),
+        _ => Err(input.error("`setter` requires `name` and `fields`")),
+    }
+}
+
+/// Parse comma-separated arguments like `key`, `key = value` or `key(...)`.
+/// The given function should consume everything after the key.
+pub fn parse_keyed_args<F>(input: ParseStream, mut f: F) -> syn::Result<()>
+where
+    F: FnMut(&Ident, ParseStream) -> syn::Result<()>,
+{
+    while !input.is_empty() {
+        let key = input.call(Ident::parse_any)?;
+        f(&key, input)?;
+        if input.is_empty() {
+            break;
+        }
+        input.parse::<Token![,]>()?;
+    }
+    Ok(())
 }
 
 pub fn get_documents(attrs: &[Attribute]) -> Vec<Attribute> {
@@ -142,10 +636,35 @@ pub fn get_documents(attrs: &[Attribute]) -> Vec<Attribute> {
 }
 
 impl FieldAttributes {
+    /// Whether the field can be validated when the structure is built.
+    pub fn lazy_validated(&self) -> bool {
+        self.validator_async.is_some()
+            || ((self.validator.is_some() || self.conversion.is_some())
+                && !(self.setters & (Setters::LAZY | Setters::ASYNC)).is_empty())
+    }
+
     fn validate(&self) -> Result<(), String> {
-        if self.vis == FieldVisibility::Hidden && self.default.is_none() {
+        if self.required && self.default.is_some() {
+            Err(String::from("`required` can't be used with `default`."))
+        } else if self.each.is_some()
+            && (self.validator.is_some()
+                || self.validator_async.is_some()
+                || self.validator_context.is_some())
+        {
+            Err(String::from("`each` setter can't be used with validators."))
+        } else if self.each.is_some() && self.required {
+            Err(String::from("`each` setter can't be used with `required`."))
+        } else if self.conversion.is_some() && self.use_into {
+            Err(String::from(
+                "`into` can't be used with fallible conversions.",
+            ))
+        } else if self.conversion.is_some() && self.wrap {
+            Err(String::from(
+                "`wrap` can't be used with fallible conversions.",
+            ))
+        } else if self.setters.contains(Setters::VALUE | Setters::FLAG) {
             Err(String::from(
-                "`hidden` attribute requires `default` attribute.",
+                "`flag` setter can't be used with `value` setter.",
             ))
         } else {
             Ok(())
@@ -18,7 +18,7 @@ impl<'a> BuilderDecl<'a> {
 impl<'a> ToTokens for BuilderDecl<'a> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let ident = &self.input.ident;
-        let vis = &self.input.vis;
+        let vis = self.input.builder_vis();
         let builder_name = self.input.builder_name();
         let where_clause = &self.input.generics.where_clause;
 
@@ -50,5 +50,34 @@ impl<'a> ToTokens for BuilderDecl<'a> {
                 #(#builder_fields),*
             }
         });
+
+        // Resolved fields are returned instead of the structure.
+        if self.input.attrs.build_fn.skip {
+            let parts_name = self.input.parts_name();
+            let generics = &self.input.generics;
+            let fields = self
+                .input
+                .required_fields
+                .iter()
+                .chain(self.input.optional_fields.iter())
+                .map(|f| {
+                    let (ident, ty) = (&f.ident, &f.ty);
+                    quote! { pub #ident: #ty }
+                });
+            let build_name = match &self.input.attrs.build_fn.name {
+                Some(name) => name.to_string(),
+                None => String::from("into_parts"),
+            };
+            let docs = format!(
+                " Resolved fields of `{}`, returned by `{}`.",
+                ident, build_name
+            );
+            tokens.extend(quote! {
+                #[doc=#docs]
+                #vis struct #parts_name #generics #where_clause {
+                    #(#fields),*
+                }
+            });
+        }
     }
 }
@@ -1,29 +1,50 @@
 use crate::{
-    attributes::{FieldVisibility, Setters},
-    field::Field,
+    attributes::{Conversion, FieldVisibility, MultiSetter, Setters},
+    field::{make_ident, Field},
+    struct_impl::default_init_arg,
     struct_input::StructInput,
 };
 
-use core::str::FromStr;
-use proc_macro2::{Ident, Span, TokenStream};
+use core::{cell::RefCell, str::FromStr};
+use proc_macro2::{Ident, Literal, Span, TokenStream};
 use quote::ToTokens;
-use syn::{parse_quote, spanned::Spanned, Attribute};
+use syn::{ext::IdentExt, parse_quote, spanned::Spanned, Attribute, Pat, Visibility};
 
 pub struct BuilderFunctions<'a> {
     pub input: &'a StructInput,
+    /// Names of the written functions and the fields they belong to, to detect collisions.
+    names: RefCell<Vec<(String, String)>>,
+    /// `maybe_` setters, which are written after the other functions unless their names collide.
+    maybes: RefCell<Vec<(Ident, String, TokenStream)>>,
+}
+
+/// A setter function setting fields immediately.
+struct ValueSetter<'f> {
+    name: Ident,
+    vis: Visibility,
+    /// Generic parameters of the function.
+    generics: Vec<TokenStream>,
+    /// Arguments of the function.
+    args: TokenStream,
+    /// Indices of fields to set, and expressions of their types evaluated from the arguments.
+    /// The expressions may return `Err(String)` if the conversion fails.
+    values: Vec<(usize, &'f Field, TokenStream)>,
+    /// The argument is `Option` of the value, and the field is not changed for `None`.
+    maybe: bool,
+    documents: Vec<Attribute>,
 }
 
 impl<'a> ToTokens for BuilderFunctions<'a> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
-        let all_builder_fields = self
+        let all_builder_fields = self.all_builder_fields();
+        // Fields whose setters are replaced by setters setting several fields.
+        let replaced = self
             .input
-            .required_fields
+            .attrs
+            .setters
             .iter()
-            .chain(self.input.optional_fields.iter())
-            .map(|f| {
-                let ident = &f.ident;
-                quote! { #ident: self.#ident }
-            })
+            .filter(|s| s.replace)
+            .flat_map(|s| s.fields.iter())
             .collect::<Vec<_>>();
 
         let mut index = 0;
@@ -31,37 +52,168 @@ impl<'a> ToTokens for BuilderFunctions<'a> {
             .required_fields
             .iter()
             .chain(self.input.optional_fields.iter())
-            .for_each(move |f| {
-                if f.attrs.vis == FieldVisibility::Hidden {
+            .for_each(|f| {
+                if matches!(f.attrs.vis, FieldVisibility::Hidden) {
                     index += 1;
                     return;
                 }
                 let mut builder_fields = all_builder_fields.clone();
+                if replaced.contains(&&f.ident) {
+                    if !(f.attrs.setters & Setters::EACH).is_empty() {
+                        self.write_each_setter(tokens, f);
+                    }
+                    index += 1;
+                    return;
+                }
 
                 if !(f.attrs.setters & Setters::VALUE).is_empty() {
                     self.write_value_setter(tokens, f, index, &mut builder_fields);
                 }
+                if !(f.attrs.setters & Setters::FLAG).is_empty() {
+                    self.write_flag_setter(tokens, f, index, &mut builder_fields);
+                }
+                // Fields having default values can be set conditionally.
+                let maybe = if f.attrs.default.is_some() {
+                    vec![false, true]
+                } else {
+                    vec![false]
+                };
                 if !(f.attrs.setters & Setters::LAZY).is_empty() {
-                    self.write_lazy_setter(tokens, f, index, &mut builder_fields);
+                    maybe.iter().for_each(|m| {
+                        self.write_lazy_setter(tokens, f, index, &mut builder_fields, *m)
+                    });
                 }
                 if !(f.attrs.setters & Setters::ASYNC).is_empty() {
-                    self.write_async_setter(tokens, f, index, &mut builder_fields);
+                    maybe.iter().for_each(|m| {
+                        self.write_async_setter(tokens, f, index, &mut builder_fields, *m)
+                    });
+                }
+                if !(f.attrs.setters & Setters::EACH).is_empty() {
+                    self.write_each_setter(tokens, f);
+                }
+                self.write_custom_setters(tokens, f, index, &mut builder_fields);
+                if f.attrs.overridable && f.attrs.default.is_some() {
+                    self.write_reset_fn(tokens, f, index);
+                }
+                if f.attrs.getter {
+                    self.write_getters(tokens, f, index);
+                }
+                if f.attrs.map {
+                    self.write_map_fn(tokens, f, index);
                 }
                 index += 1;
             });
+        self.input
+            .attrs
+            .setters
+            .iter()
+            .for_each(|setter| self.write_multi_setter(tokens, setter));
+        self.write_maybe_fns(tokens);
+        self.check_names();
     }
 }
 
 impl<'a> BuilderFunctions<'a> {
     pub fn new(input: &'a StructInput) -> Self {
-        Self { input }
+        Self {
+            input,
+            names: RefCell::new(vec![]),
+            maybes: RefCell::new(vec![]),
+        }
+    }
+
+    /// Record the name of a function written for `owner`.
+    fn register(&self, name: &Ident, owner: String) {
+        self.names
+            .borrow_mut()
+            .push((name.unraw().to_string(), owner));
     }
 
-    fn documents(f: &Field, _setter: Setters) -> Vec<Attribute> {
+    /// Functions having the same names can't be defined on the builder.
+    /// Implicit names like `x_lazy` may collide with names of other fields.
+    fn check_names(&self) {
+        let names = self.names.borrow();
+        names.iter().enumerate().for_each(|(i, (name, owner))| {
+            if let Some((_, other)) = names[..i].iter().find(|(n, _)| n == name) {
+                unimplemented!(
+                    "The builder has functions named `{}` for `{}` and `{}`. \
+                    Rename one of them via `#[setter(rename = \"...\")]`.",
+                    name,
+                    other,
+                    owner
+                )
+            }
+        });
+    }
+
+    /// Write a function, or defer it if it is a `maybe_` setter.
+    fn write_fn(
+        &self,
+        tokens: &mut TokenStream,
+        name: &Ident,
+        owner: String,
+        maybe: bool,
+        function: TokenStream,
+    ) {
+        if maybe {
+            self.maybes
+                .borrow_mut()
+                .push((name.clone(), owner, function));
+        } else {
+            self.register(name, owner);
+            tokens.extend(function);
+        }
+    }
+
+    /// Names of `maybe_` setters are implicit, so they are skipped if they collide with names of
+    /// other functions, like setters of a field named `maybe_x`.
+    fn write_maybe_fns(&self, tokens: &mut TokenStream) {
+        self.maybes
+            .borrow_mut()
+            .drain(..)
+            .for_each(|(name, owner, function)| {
+                let name_str = name.unraw().to_string();
+                if !self.names.borrow().iter().any(|(n, _)| *n == name_str) {
+                    self.register(&name, owner);
+                    tokens.extend(function);
+                }
+            });
+    }
+
+    /// Name of the setter taking `Option` of the value.
+    fn maybe_name(name: &Ident) -> Ident {
+        make_ident(&format!("maybe_{}", name.unraw()))
+    }
+
+    fn all_builder_fields(&self) -> Vec<TokenStream> {
+        self.all_fields()
+            .map(|f| {
+                let ident = &f.ident;
+                quote! { #ident: self.#ident }
+            })
+            .collect()
+    }
+
+    fn documents(f: &Field, setter: Setters) -> Vec<Attribute> {
         let mut docs: Vec<Attribute> = Vec::new();
 
+        if setter == Setters::FLAG {
+            let doc = format!(" Set `{}` to `true`.\n\n", f.ident);
+            docs.push(parse_quote!(#[doc=#doc]));
+            docs.append(f.documents().as_mut());
+            return docs;
+        }
+        if setter == Setters::EACH {
+            let doc = format!(
+                " Add items to `{}`.\n It can be called any number of times.",
+                f.ident
+            );
+            docs.push(parse_quote!(#[doc=#doc]));
+            return docs;
+        }
+
         let default = match f.attrs.default.as_ref() {
-            Some((expr, _)) => format!("\n - Default: `{}`", expr.into_token_stream().to_string()),
+            Some((expr, _)) => format!("\n - Default: `{}`", expr.into_token_stream()),
             None => String::from(""),
         };
         let doc = format!(
@@ -77,129 +229,871 @@ impl<'a> BuilderFunctions<'a> {
         docs
     }
 
+    fn maybe_documents(f: &Field) -> Vec<Attribute> {
+        let mut docs = Self::documents(f, Setters::VALUE);
+        docs.insert(
+            1,
+            parse_quote!(#[doc=" If `None` is given, the field keeps its default value.\n\n"]),
+        );
+        docs
+    }
+
+    /// A setter which is evaluated when the structure is built.
+    /// `value` is an expression to get the value, and `is_async` means it can be awaited.
+    fn deferred_setter(f: &Field, value: TokenStream, is_async: bool) -> TokenStream {
+        let ty = &f.ty;
+        let value = f.transformed(value, false);
+        match (&f.attrs.validator, &f.attrs.validator_async, is_async) {
+            (v, Some(av), _) => {
+                let validate = v.as_ref().map(|v| {
+                    let call = v.call_deferred(ty, quote! {value});
+                    quote_spanned! { v.span() =>
+                        let value = match #call {
+                            Ok(value) => value,
+                            Err(e) => return Err(e),
+                        };
+                    }
+                });
+                quote_spanned! { av.span() =>
+                    ::builder_pattern::setter::Setter::AsyncValidated(
+                        Box::new(move || Box::pin(async move {
+                            let value: #ty = #value;
+                            #validate
+                            (#av)(value).await
+                        }))
+                    )
+                }
+            }
+            (Some(v), None, false) => {
+                let call = v.call_deferred(ty, value);
+                quote_spanned! { v.span() =>
+                    ::builder_pattern::setter::Setter::LazyValidated(
+                        Box::new(move || #call)
+                    )
+                }
+            }
+            (Some(v), None, true) => {
+                let call = v.call_deferred(ty, value);
+                quote_spanned! { v.span() =>
+                    ::builder_pattern::setter::Setter::AsyncValidated(
+                        Box::new(move || Box::pin(async move { #call }))
+                    )
+                }
+            }
+            (None, None, false) if f.attrs.conversion.is_some() => quote! {
+                ::builder_pattern::setter::Setter::LazyValidated(
+                    Box::new(move || Ok(#value))
+                )
+            },
+            (None, None, true) if f.attrs.conversion.is_some() => quote! {
+                ::builder_pattern::setter::Setter::AsyncValidated(
+                    Box::new(move || Box::pin(async move { Ok(#value) }))
+                )
+            },
+            (None, None, false) => quote! {
+                ::builder_pattern::setter::Setter::Lazy(
+                    Box::new(move || #value)
+                )
+            },
+            (None, None, true) => quote! {
+                ::builder_pattern::setter::Setter::Async(
+                    Box::new(move || Box::pin(async move { #value }))
+                )
+            },
+        }
+    }
+
+    /// Generic parameters and the type of values given by lazy and async setters.
+    fn deferred_argument(f: &Field) -> (Option<TokenStream>, TokenStream) {
+        let setter_ty = f.setter_ty();
+        if let Some(bounds) = f.trait_object_bounds() {
+            return (Some(quote! { WrapType: #bounds, }), quote! {WrapType});
+        }
+        match f.attrs.conversion {
+            Some(Conversion::TryInto) => (
+                Some(quote! { ConvType: ::core::convert::TryInto<#setter_ty>, }),
+                quote! {ConvType},
+            ),
+            Some(Conversion::Parse) => (
+                Some(quote! { ConvType: ::core::convert::AsRef<str>, }),
+                quote! {ConvType},
+            ),
+            None if f.attrs.use_into => (
+                Some(quote! { IntoType: Into<#setter_ty>, }),
+                quote! {IntoType},
+            ),
+            None => (None, quote! {#setter_ty}),
+        }
+    }
+
+    /// An expression converting `value` into the type of setters.
+    /// If the conversion fails, it returns an error of `String`, or `&'static str` if `is_deferred`.
+    pub fn convert(f: &Field, value: TokenStream, is_deferred: bool) -> TokenStream {
+        let setter_ty = f.setter_ty();
+        let converted = match f.attrs.conversion {
+            Some(Conversion::TryInto) => quote! {
+                ::core::convert::TryInto::<#setter_ty>::try_into(#value)
+            },
+            Some(Conversion::Parse) => quote! {
+                <#setter_ty as ::core::str::FromStr>::from_str(
+                    ::core::convert::AsRef::<str>::as_ref(&#value)
+                )
+            },
+            None if f.trait_object_bounds().is_some() => return value,
+            None => return quote! { #value.into() },
+        };
+        let error = if is_deferred {
+            // The error of the conversion can't be `&'static str`,
+            // so the message names the field and the type instead.
+            let name = f.ident.unraw().to_string();
+            quote! {
+                Err(_) => return Err(concat!(
+                    "Conversion of `", #name, "` into `", stringify!(#setter_ty), "` failed."
+                ))
+            }
+        } else {
+            quote! { Err(e) => return Err(format!("Conversion failed: {:?}", e)) }
+        };
+        quote! {
+            match #converted {
+                Ok(value) => value,
+                #error,
+            }
+        }
+    }
+
+    /// Generics of the builder for the function setting fields of `indices`.
+    /// It returns the generics of the function, the builder before and after setting.
     fn get_generics(
         &self,
-        f: &Field,
-        index: usize,
+        indices: &[usize],
     ) -> (Vec<TokenStream>, Vec<TokenStream>, Vec<TokenStream>) {
         let all_generics = self.input.all_generics().collect::<Vec<_>>();
-        let ty = &f.ty;
-        let mut other_generics = all_generics.clone();
-        other_generics.remove(index);
+        let fields = self.all_fields().collect::<Vec<_>>();
+        let other_generics = all_generics
+            .iter()
+            .enumerate()
+            .filter_map(|(i, g)| {
+                if !indices.contains(&i) || fields[i].attrs.overridable {
+                    Some(g.clone())
+                } else if fields[i].attrs.preset {
+                    Some(quote! { #g: ::builder_pattern::setter::Unset })
+                } else {
+                    None
+                }
+            })
+            .collect();
         let mut before_generics = all_generics.clone();
-        before_generics[index] = TokenStream::from_str("()").unwrap();
         let mut after_generics = all_generics;
-        after_generics[index] = quote! {#ty};
+        for &index in indices {
+            // Setters of overridable fields are provided for any states,
+            // and setters of fields set by presets are provided for `()` and `Preset<T>`.
+            if !fields[index].attrs.overridable && !fields[index].attrs.preset {
+                before_generics[index] = TokenStream::from_str("()").unwrap();
+            }
+            after_generics[index] = self.input.set_slot(fields[index]);
+        }
 
         (other_generics, before_generics, after_generics)
     }
 
+    fn all_fields(&self) -> impl Iterator<Item = &Field> {
+        self.input
+            .required_fields
+            .iter()
+            .chain(self.input.optional_fields.iter())
+    }
+
+    /// Generics of the builder in which the field of `index` is in the state of `slot`.
+    /// It returns the generics of the implementation and the states of the builder.
+    fn set_generics(
+        &self,
+        index: usize,
+        slot: TokenStream,
+    ) -> (Vec<TokenStream>, Vec<TokenStream>) {
+        let all_generics = self.input.all_generics().collect::<Vec<_>>();
+        let mut other_generics = all_generics.clone();
+        other_generics.remove(index);
+        let mut set_generics = all_generics;
+        set_generics[index] = slot;
+        (other_generics, set_generics)
+    }
+
+    /// Type of the builder whose fields are in `states`.
+    fn builder_type(
+        &self,
+        states: &[TokenStream],
+        async_generic: TokenStream,
+        validator_option: TokenStream,
+    ) -> TokenStream {
+        let builder_name = self.input.builder_name();
+        let fn_lifetime = self.input.fn_lifetime();
+        let lifetimes = self.input.lifetimes();
+        let ty_tokens = self.input.tokenize_types();
+        quote! {
+            #builder_name <
+                #fn_lifetime,
+                #(#lifetimes,)*
+                #ty_tokens
+                #(#states,)*
+                #async_generic,
+                #validator_option
+            >
+        }
+    }
+
+    /// Expression of the builder having `builder_fields`.
+    fn builder_expr(&self, builder_fields: &[TokenStream]) -> TokenStream {
+        let builder_name = self.input.builder_name();
+        quote! {
+            #builder_name {
+                _phantom: ::core::marker::PhantomData,
+                #(#builder_fields),*
+            }
+        }
+    }
+
+    /// An implementation block of the builder whose fields are in `states`, generic over
+    /// `generics` of the states and the markers.
+    fn impl_builder(
+        &self,
+        generics: &[TokenStream],
+        states: &[TokenStream],
+        body: TokenStream,
+    ) -> TokenStream {
+        let fn_lifetime = self.input.fn_lifetime();
+        let impl_tokens = self.input.tokenize_impl();
+        let where_clause = &self.input.generics.where_clause;
+        let builder_type =
+            self.builder_type(states, quote! {AsyncFieldMarker}, quote! {ValidatorOption});
+        quote! {
+            impl <
+                #fn_lifetime,
+                #impl_tokens
+                #(#generics,)*
+                AsyncFieldMarker,
+                ValidatorOption
+            > #builder_type
+                #where_clause
+            {
+                #body
+            }
+        }
+    }
+
+    /// Generic parameters and the type of the argument of value setters.
+    /// `suffix` is appended to the names of generic parameters.
+    pub fn value_argument(f: &Field, suffix: &str) -> (Vec<TokenStream>, TokenStream) {
+        let setter_ty = f.setter_ty();
+        let param = |name: &str| Ident::new(&format!("{}{}", name, suffix), Span::call_site());
+        match f.attrs.conversion {
+            None if f.trait_object_bounds().is_some() => {
+                let (wrap_ty, bounds) = (param("WrapType"), f.trait_object_bounds());
+                (vec![quote! {#wrap_ty: #bounds}], quote! {#wrap_ty})
+            }
+            Some(Conversion::TryInto) => {
+                let (conv_ty, error_ty) = (param("ConvType"), param("ErrorType"));
+                (
+                    vec![
+                        quote! {#conv_ty: ::core::convert::TryInto<#setter_ty, Error = #error_ty>},
+                        quote! {#error_ty: ::core::fmt::Debug},
+                    ],
+                    quote! {#conv_ty},
+                )
+            }
+            Some(Conversion::Parse) => (vec![], quote! {&str}),
+            None if f.attrs.use_into => {
+                let into_ty = param("IntoType");
+                (vec![quote! {#into_ty: Into<#setter_ty>}], quote! {#into_ty})
+            }
+            None => (vec![], quote! {#setter_ty}),
+        }
+    }
+
     fn write_value_setter(
         &self,
         tokens: &mut TokenStream,
         f: &Field,
         index: usize,
-        builder_fields: &mut Vec<TokenStream>,
+        builder_fields: &mut [TokenStream],
+    ) {
+        let (generics, arg_type) = Self::value_argument(f, "");
+        let value = f.wrap_value(Self::convert(f, quote! {value}, false));
+        let name = self.input.setter_name(f, "");
+        let setter = ValueSetter {
+            name: name.clone(),
+            vis: f.vis.clone(),
+            generics: generics.clone(),
+            args: quote! { value: #arg_type },
+            values: vec![(index, f, value.clone())],
+            maybe: false,
+            documents: Self::documents(f, Setters::VALUE),
+        };
+        self.write_value_fn(tokens, builder_fields, setter);
+
+        // Fields having default values can be set conditionally.
+        if f.attrs.default.is_some() {
+            let setter = ValueSetter {
+                name: Self::maybe_name(&name),
+                vis: f.vis.clone(),
+                generics,
+                args: quote! { value: Option<#arg_type> },
+                values: vec![(index, f, value)],
+                maybe: true,
+                documents: Self::maybe_documents(f),
+            };
+            self.write_value_fn(tokens, builder_fields, setter);
+        }
+    }
+
+    fn write_flag_setter(
+        &self,
+        tokens: &mut TokenStream,
+        f: &Field,
+        index: usize,
+        builder_fields: &mut [TokenStream],
+    ) {
+        let setters = vec![
+            ValueSetter {
+                name: self.input.setter_name(f, ""),
+                vis: f.vis.clone(),
+                generics: vec![],
+                args: TokenStream::new(),
+                values: vec![(index, f, quote! {true})],
+                maybe: false,
+                documents: Self::documents(f, Setters::FLAG),
+            },
+            ValueSetter {
+                name: self.input.setter_name(f, "_if"),
+                vis: f.vis.clone(),
+                generics: vec![],
+                args: quote! { value: bool },
+                values: vec![(index, f, quote! {value})],
+                maybe: false,
+                documents: Self::documents(f, Setters::VALUE),
+            },
+        ];
+        setters
+            .into_iter()
+            .for_each(|setter| self.write_value_fn(tokens, builder_fields, setter));
+    }
+
+    /// Setters given via `#[setter(name = "...", with = closure)]`.
+    fn write_custom_setters(
+        &self,
+        tokens: &mut TokenStream,
+        f: &Field,
+        index: usize,
+        builder_fields: &mut [TokenStream],
     ) {
+        f.attrs.custom_setters.iter().for_each(|(name, closure)| {
+            let (args, idents): (Vec<_>, Vec<_>) = closure
+                .inputs
+                .iter()
+                .enumerate()
+                .map(|(i, input)| match input {
+                    Pat::Type(t) => {
+                        let ident = match &*t.pat {
+                            Pat::Ident(p) => p.ident.clone(),
+                            _ => Ident::new(&format!("arg{}", i + 1), Span::call_site()),
+                        };
+                        let ty = &t.ty;
+                        (quote! { #ident: #ty }, ident)
+                    }
+                    _ => unreachable!(),
+                })
+                .unzip();
+            let mut documents: Vec<Attribute> = vec![];
+            let doc = format!(
+                " # {}\n Set `{}` via `{}`.\n\n",
+                name,
+                f.ident,
+                closure.into_token_stream()
+            );
+            documents.push(parse_quote!(#[doc=#doc]));
+            documents.append(f.documents().as_mut());
+            let setter = ValueSetter {
+                name: name.clone(),
+                vis: f.vis.clone(),
+                generics: vec![],
+                args: quote! { #(#args),* },
+                values: vec![(
+                    index,
+                    f,
+                    quote_spanned! { closure.span() => (#closure)(#(#idents),*) },
+                )],
+                maybe: false,
+                documents,
+            };
+            self.write_value_fn(tokens, builder_fields, setter);
+        });
+    }
+
+    /// Functions to check the state of the field and peek the value.
+    fn write_getters(&self, tokens: &mut TokenStream, f: &Field, index: usize) {
         let (ident, ty, vis) = (&f.ident, &f.ty, &f.vis);
-        let builder_name = self.input.builder_name();
+        let ref_name = make_ident(&format!("{}_ref", f.name()));
+        let is_set_name = make_ident(&format!("is_{}_set", f.name()));
+        self.register(&ref_name, ident.unraw().to_string());
+        self.register(&is_set_name, ident.unraw().to_string());
+        let struct_name = &self.input.ident;
         let where_clause = &self.input.generics.where_clause;
         let lifetimes = self.input.lifetimes();
-        let fn_lifetime = self.input.fn_lifetime();
         let impl_tokens = self.input.tokenize_impl();
         let ty_tokens = self.input.tokenize_types();
-        let (other_generics, before_generics, after_generics) = self.get_generics(f, index);
-        let (arg_type_gen, arg_type) = if f.attrs.use_into {
-            (
-                Some(quote! {<IntoType: Into<#ty>>}),
-                TokenStream::from_str("IntoType").unwrap(),
-            )
-        } else {
-            (None, quote! {#ty})
+        let all_generics = self.input.all_generics().collect::<Vec<_>>();
+        let state = &all_generics[index];
+        let field_state = {
+            let index = Literal::usize_unsuffixed(index);
+            quote! {
+                ::builder_pattern::setter::FieldState<
+                    #struct_name <#(#lifetimes,)* #ty_tokens>,
+                    #index
+                >
+            }
         };
-        let documents = Self::documents(f, Setters::VALUE);
+        let ref_doc = format!(
+            " Returns the value of `{}` if it is set by the value setter.\n Lazy and async values are not evaluated, so `None` is returned for them.",
+            ident
+        );
+        let is_set_doc = format!(" Whether `{}` is set.", ident);
 
-        let (ret_type, ret_expr) = match &f.attrs.validator {
-            Some(v) => {
-                builder_fields[index] = quote! {
-                    #ident: Some(
-                        ::builder_pattern::setter::Setter::Value(value)
-                    )
+        // `Set<T>` is used for the set state if the type may be `()`.
+        if !self.input.may_be_unit(ty) {
+            tokens.extend(quote! {
+                impl <#impl_tokens> #field_state for #ty
+                    #where_clause
+                {
+                    const IS_SET: bool = true;
+                }
+            });
+        }
+
+        tokens.extend(self.impl_builder(
+            &all_generics,
+            &all_generics,
+            quote! {
+                #[doc=#is_set_doc]
+                #vis const fn #is_set_name(&self) -> bool
+                where
+                    #state: #field_state,
+                {
+                    <#state as #field_state>::IS_SET
+                }
+            },
+        ));
+        self.input.filled_slots(f).into_iter().for_each(|slot| {
+            let (other_generics, set_generics) = self.set_generics(index, slot);
+            tokens.extend(self.impl_builder(
+                &other_generics,
+                &set_generics,
+                quote! {
+                    #[doc=#ref_doc]
+                    #vis fn #ref_name(&self) -> Option<&#ty> {
+                        match &self.#ident {
+                            Some(::builder_pattern::setter::Setter::Value(value)) => Some(value),
+                            _ => None,
+                        }
+                    }
+                },
+            ));
+        });
+    }
+
+    /// A function transforming the value of the field which is set.
+    /// The validators of the field are applied to the transformed value.
+    fn write_map_fn(&self, tokens: &mut TokenStream, f: &Field, index: usize) {
+        let map_name = make_ident(&format!("map_{}", f.name()));
+        self.register(&map_name, f.ident.unraw().to_string());
+        self.input
+            .filled_slots(f)
+            .into_iter()
+            .for_each(|slot| tokens.extend(self.map_fn(f, index, &map_name, slot)));
+    }
+
+    /// The function transforming the value of the field in the state of `slot`.
+    fn map_fn(&self, f: &Field, index: usize, map_name: &Ident, slot: TokenStream) -> TokenStream {
+        let (ident, ty, vis) = (&f.ident, &f.ty, &f.vis);
+        let fn_lifetime = self.input.fn_lifetime();
+        let (other_generics, set_generics) = self.set_generics(index, slot);
+        let doc = format!(
+            " # {}\n Transform the value of `{}`.\n For lazy and async values, it is applied after they are evaluated.",
+            map_name, ident
+        );
+        let deferred = !(f.attrs.setters & (Setters::LAZY | Setters::ASYNC)).is_empty();
+
+        let function = match (&f.attrs.validator, &f.attrs.validator_async) {
+            (None, None) => quote! {
+                #vis fn #map_name<MapType>(mut self, f: MapType) -> Self
+                where
+                    #ty: #fn_lifetime,
+                    MapType: #fn_lifetime + ::core::ops::FnOnce(#ty) -> #ty,
+                {
+                    self.#ident = self.#ident.map(|setter| setter.map(f));
+                    self
+                }
+            },
+            // Values are validated immediately like the value setter.
+            (Some(v), None) if !deferred => {
+                let call = v.call(ty, quote! {value});
+                let validate = quote_spanned! { v.span() =>
+                    #[allow(clippy::useless_conversion)]
+                    let value: #ty = match #call {
+                        Ok(value) => value,
+                        Err(e) => return Err(format!("Validation failed: {:?}", e)),
+                    };
                 };
-                (
-                    quote! {
-                        Result<#builder_name <
-                            #fn_lifetime,
-                            #(#lifetimes,)*
-                            #ty_tokens
-                            #(#after_generics,)*
-                            AsyncFieldMarker,
-                            ValidatorOption
-                        >, String>
-                    },
-                    quote_spanned! { v.span() =>
-                        #[allow(clippy::useless_conversion)]
-                        match #v (value.into()) {
-                            Ok(value) => Ok(
-                                #builder_name {
-                                    _phantom: ::core::marker::PhantomData,
-                                    #(#builder_fields),*
-                                }),
-                            Err(e) => Err(format!("Validation failed: {:?}", e))
+                quote! {
+                    #vis fn #map_name<MapType>(mut self, f: MapType) -> Result<Self, String>
+                    where
+                        MapType: ::core::ops::FnOnce(#ty) -> #ty,
+                    {
+                        if let Some(setter) = self.#ident {
+                            let value = match setter {
+                                ::builder_pattern::setter::Setter::Value(value) => f(value),
+                                ::builder_pattern::setter::Setter::Lazy(value) => f(value()),
+                                _ => unreachable!(),
+                            };
+                            #validate
+                            self.#ident = Some(::builder_pattern::setter::Setter::Value(value));
                         }
-                    },
-                )
+                        Ok(self)
+                    }
+                }
             }
-            None => {
-                builder_fields[index] = quote! {
-                    #ident: Some(
-                        ::builder_pattern::setter::Setter::Value(value.into())
+            // Errors of the validator may not be `&'static str`, so it can't be deferred.
+            // The value is resolved asynchronously, and validated immediately.
+            (Some(v), Some(av)) if !deferred => {
+                let call = v.call(ty, quote! {value});
+                let validate = quote_spanned! { v.span() =>
+                    #[allow(clippy::useless_conversion)]
+                    let value: #ty = match #call {
+                        Ok(value) => value,
+                        Err(e) => return Err(format!("Validation failed: {:?}", e)),
+                    };
+                };
+                let setter = quote_spanned! { av.span() =>
+                    ::builder_pattern::setter::Setter::AsyncValidated(
+                        Box::new(move || Box::pin(async move { (#av)(value).await }))
                     )
                 };
-                (
-                    quote! {
-                        #builder_name <
-                            #fn_lifetime,
-                            #(#lifetimes,)*
-                            #ty_tokens
-                            #(#after_generics,)*
-                            AsyncFieldMarker,
-                            ValidatorOption
-                        >
-                    },
-                    quote! {
-                        #builder_name {
-                            _phantom: ::core::marker::PhantomData,
-                            #(#builder_fields),*
+                let mut builder_fields = self.all_builder_fields();
+                builder_fields[index] = quote! {#ident};
+                let ret_type = self.builder_type(
+                    &set_generics,
+                    quote! {::builder_pattern::setter::AsyncBuilderMarker},
+                    quote! {::builder_pattern::setter::HavingLazyValidator},
+                );
+                let ret_expr = self.builder_expr(&builder_fields);
+                quote! {
+                    #[allow(clippy::redundant_closure_call)]
+                    #vis async fn #map_name<MapType>(self, f: MapType) -> Result<#ret_type, String>
+                    where
+                        #ty: #fn_lifetime,
+                        MapType: ::core::ops::FnOnce(#ty) -> #ty,
+                    {
+                        let #ident = match self.#ident {
+                            Some(setter) => {
+                                let value = match setter {
+                                    ::builder_pattern::setter::Setter::Value(value) => value,
+                                    ::builder_pattern::setter::Setter::Lazy(value) => value(),
+                                    ::builder_pattern::setter::Setter::AsyncValidated(value) => {
+                                        match value().await {
+                                            Ok(value) => value,
+                                            Err(e) => {
+                                                return Err(format!("Validation failed: {:?}", e))
+                                            }
+                                        }
+                                    }
+                                    _ => unreachable!(),
+                                };
+                                let value = f(value);
+                                #validate
+                                Some(#setter)
+                            }
+                            None => None,
+                        };
+                        Ok(#ret_expr)
+                    }
+                }
+            }
+            (v, av) => {
+                let mut builder_fields = self.all_builder_fields();
+                builder_fields[index] = match av {
+                    Some(av) => {
+                        let validate = v.as_ref().map(|v| {
+                            let call = v.call_deferred(ty, quote! {value});
+                            quote_spanned! { v.span() =>
+                                let value = match #call {
+                                    Ok(value) => value,
+                                    Err(e) => return Err(e),
+                                };
+                            }
+                        });
+                        quote_spanned! { av.span() =>
+                            #ident: self.#ident.map(|setter| {
+                                setter.map_async_validated(f, move |value: #ty| async move {
+                                    #validate
+                                    (#av)(value).await
+                                })
+                            })
                         }
-                    },
-                )
+                    }
+                    None => {
+                        let v = v.as_ref().unwrap();
+                        let call = v.call_deferred(ty, quote! {value});
+                        quote_spanned! { v.span() =>
+                            #ident: self.#ident.map(|setter| {
+                                setter.map_validated(f, move |value: #ty| #call)
+                            })
+                        }
+                    }
+                };
+                let async_generic = if av.is_some() {
+                    quote! {::builder_pattern::setter::AsyncBuilderMarker}
+                } else {
+                    quote! {AsyncFieldMarker}
+                };
+                let ret_type = self.builder_type(
+                    &set_generics,
+                    async_generic,
+                    quote! {::builder_pattern::setter::HavingLazyValidator},
+                );
+                let ret_expr = self.builder_expr(&builder_fields);
+                quote! {
+                    #[allow(clippy::redundant_closure_call)]
+                    #vis fn #map_name<MapType>(self, f: MapType) -> #ret_type
+                    where
+                        #ty: #fn_lifetime,
+                        MapType: #fn_lifetime + ::core::ops::FnOnce(#ty) -> #ty,
+                    {
+                        #ret_expr
+                    }
+                }
             }
         };
 
-        tokens.extend(quote! {
-            impl <
-                #fn_lifetime,
-                #impl_tokens
-                #(#other_generics,)*
-                AsyncFieldMarker,
-                ValidatorOption
-            > #builder_name <
-                #fn_lifetime,
-                #(#lifetimes,)*
-                #ty_tokens
-                #(#before_generics,)*
-                AsyncFieldMarker,
-                ValidatorOption
-            >
-                #where_clause
-            {
-                #(#documents)*
-                #vis fn #ident #arg_type_gen(self, value: #arg_type) -> #ret_type {
+        self.impl_builder(
+            &other_generics,
+            &set_generics,
+            quote! {
+                #[doc=#doc]
+                #function
+            },
+        )
+    }
+
+    /// A function restoring the default value of the field.
+    fn write_reset_fn(&self, tokens: &mut TokenStream, f: &Field, index: usize) {
+        let (ident, vis) = (&f.ident, &f.vis);
+        let reset_name = make_ident(&format!("reset_{}", f.name()));
+        self.register(&reset_name, ident.unraw().to_string());
+        let all_generics = self.input.all_generics().collect::<Vec<_>>();
+        let mut after_generics = all_generics.clone();
+        after_generics[index] = TokenStream::from_str("()").unwrap();
+        let mut builder_fields = self.all_builder_fields();
+        builder_fields[index] = default_init_arg(f);
+        let ret_type = self.builder_type(
+            &after_generics,
+            quote! {AsyncFieldMarker},
+            quote! {ValidatorOption},
+        );
+        let ret_expr = self.builder_expr(&builder_fields);
+        let doc = format!(
+            " # {}\n Restore the default value of `{}`.",
+            reset_name, ident
+        );
+
+        tokens.extend(self.impl_builder(
+            &all_generics,
+            &all_generics,
+            quote! {
+                #[doc=#doc]
+                #[allow(clippy::redundant_closure_call)]
+                #vis fn #reset_name(self) -> #ret_type {
                     #ret_expr
                 }
+            },
+        ));
+    }
+
+    /// A setter setting several fields at once, given via `#[builder(setter(...))]`.
+    fn write_multi_setter(&self, tokens: &mut TokenStream, setter: &MultiSetter) {
+        let mut builder_fields = self.all_builder_fields();
+        let mut generics = vec![];
+        let mut args = vec![];
+        let mut values = vec![];
+        let mut docs = vec![];
+        setter.fields.iter().for_each(|ident| {
+            let (index, f) = self
+                .all_fields()
+                .enumerate()
+                .find(|(_, f)| &f.ident == ident)
+                .unwrap_or_else(|| unimplemented!("Unknown field `{}`.", ident));
+            if matches!(f.attrs.vis, FieldVisibility::Hidden) {
+                unimplemented!("Hidden field `{}` can't be set.", ident)
             }
+            let (mut arg_generics, arg_type) = Self::value_argument(f, &(index + 1).to_string());
+            generics.append(&mut arg_generics);
+            args.push(quote! { #ident: #arg_type });
+            values.push((
+                index,
+                f,
+                f.wrap_value(Self::convert(f, quote! {#ident}, false)),
+            ));
+            docs.push(format!(" - `{}`: `{}`", ident, f.type_documents()));
         });
+        let doc = format!(
+            " # {}\n Set fields at once.\n{}",
+            setter.name,
+            docs.join("\n")
+        );
+        let setter = ValueSetter {
+            name: setter.name.clone(),
+            vis: self.input.builder_vis().clone(),
+            generics,
+            args: quote! { #(#args),* },
+            values,
+            maybe: false,
+            documents: vec![parse_quote!(#[doc=#doc])],
+        };
+        self.write_value_fn(tokens, &mut builder_fields, setter);
+    }
+
+    /// Statements evaluating the values, and replace `builder_fields` with them.
+    /// It returns whether the values may fail and whether they are validated asynchronously.
+    pub fn value_statements(
+        values: Vec<(usize, &Field, TokenStream)>,
+        maybe: bool,
+        builder_fields: &mut [TokenStream],
+    ) -> (Vec<TokenStream>, bool, bool) {
+        let mut fallible = false;
+        let mut validate_async = false;
+        let mut statements = vec![];
+        for (index, f, value) in values {
+            let (ident, ty) = (&f.ident, &f.ty);
+            let value = f.transformed(value, false);
+            fallible |= f.attrs.conversion.is_some();
+            statements.push(match &f.attrs.validator {
+                Some(v) => {
+                    fallible = true;
+                    let call = v.call(ty, value);
+                    quote_spanned! { v.span() =>
+                        #[allow(clippy::useless_conversion)]
+                        let #ident: #ty = match #call {
+                            Ok(value) => value,
+                            Err(e) => return Err(format!("Validation failed: {:?}", e)),
+                        };
+                    }
+                }
+                None => quote! {
+                    #[allow(clippy::useless_conversion)]
+                    let #ident: #ty = #value;
+                },
+            });
+            let setter = match &f.attrs.validator_async {
+                Some(v) => {
+                    validate_async = true;
+                    quote_spanned! { v.span() =>
+                        ::builder_pattern::setter::Setter::AsyncValidated(
+                            Box::new(move || Box::pin(async move { (#v)(#ident).await }))
+                        )
+                    }
+                }
+                None => quote! {
+                    ::builder_pattern::setter::Setter::Value(#ident)
+                },
+            };
+            builder_fields[index] = if maybe {
+                let statement = statements.pop();
+                statements.push(quote! {
+                    let #ident = match value {
+                        Some(value) => {
+                            #statement
+                            Some(#setter)
+                        }
+                        None => self.#ident,
+                    };
+                });
+                quote! { #ident: #ident }
+            } else {
+                quote! { #ident: Some(#setter) }
+            };
+        }
+        (statements, fallible, validate_async)
+    }
+
+    /// Write a setter function which sets the values immediately.
+    fn write_value_fn(
+        &self,
+        tokens: &mut TokenStream,
+        builder_fields: &mut [TokenStream],
+        setter: ValueSetter,
+    ) {
+        let ValueSetter {
+            name,
+            vis,
+            generics,
+            args,
+            values,
+            maybe,
+            documents,
+        } = setter;
+        let indices = values
+            .iter()
+            .map(|(index, _, _)| *index)
+            .collect::<Vec<_>>();
+        let owner = values
+            .iter()
+            .map(|(_, f, _)| f.ident.unraw().to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let (other_generics, before_generics, after_generics) = self.get_generics(&indices);
+        let generics = if generics.is_empty() {
+            None
+        } else {
+            Some(quote! {<#(#generics),*>})
+        };
+
+        let (statements, fallible, validate_async) =
+            Self::value_statements(values, maybe, builder_fields);
+        let (async_generic, validator_option) = if validate_async {
+            (
+                quote! {::builder_pattern::setter::AsyncBuilderMarker},
+                quote! {::builder_pattern::setter::HavingLazyValidator},
+            )
+        } else {
+            (quote! {AsyncFieldMarker}, quote! {ValidatorOption})
+        };
+        let builder_type = self.builder_type(&after_generics, async_generic, validator_option);
+        let ret_builder = self.builder_expr(builder_fields);
+        let (ret_type, ret_expr) = if fallible {
+            (
+                quote! { Result<#builder_type, String> },
+                quote! { Ok(#ret_builder) },
+            )
+        } else {
+            (builder_type, ret_builder)
+        };
+
+        self.write_fn(
+            tokens,
+            &name,
+            owner,
+            maybe,
+            self.impl_builder(
+                &other_generics,
+                &before_generics,
+                quote! {
+                    #(#documents)*
+                    #[allow(clippy::redundant_closure_call)]
+                    #vis fn #name #generics(self, #args) -> #ret_type {
+                        #(#statements)*
+                        #ret_expr
+                    }
+                },
+            ),
+        );
     }
 
     fn write_lazy_setter(
@@ -207,89 +1101,79 @@ impl<'a> BuilderFunctions<'a> {
         tokens: &mut TokenStream,
         f: &Field,
         index: usize,
-        builder_fields: &mut Vec<TokenStream>,
+        builder_fields: &mut [TokenStream],
+        maybe: bool,
     ) {
-        let (ident, ty, vis) = (&f.ident, &f.ty, &f.vis);
-        let seter_name = Ident::new(&format!("{}_lazy", &ident.to_string()), Span::call_site());
-        let builder_name = self.input.builder_name();
-        let where_clause = &self.input.generics.where_clause;
-        let lifetimes = self.input.lifetimes();
+        let (ident, vis) = (&f.ident, &f.vis);
+        let seter_name = self.input.lazy_setter_name(f);
+        let seter_name = if maybe {
+            Self::maybe_name(&seter_name)
+        } else {
+            seter_name
+        };
         let fn_lifetime = self.input.fn_lifetime();
-        let impl_tokens = self.input.tokenize_impl();
-        let ty_tokens = self.input.tokenize_types();
-        let (other_generics, before_generics, after_generics) = self.get_generics(f, index);
-        let arg_type_gen = if f.attrs.use_into {
-            quote! {<IntoType: Into<#ty>, ValType: #fn_lifetime + ::core::ops::Fn() -> IntoType>}
+        let (other_generics, before_generics, after_generics) = self.get_generics(&[index]);
+        let (arg_gen, arg_type) = Self::deferred_argument(f);
+        let arg_type_gen = quote! {<
+            #arg_gen
+            ValType: #fn_lifetime + ::core::ops::Fn() -> #arg_type
+        >};
+        let (arg_type, documents) = if maybe {
+            (quote! {Option<ValType>}, Self::maybe_documents(f))
         } else {
-            quote! {<ValType: #fn_lifetime + ::core::ops::Fn() -> #ty>}
+            (quote! {ValType}, Self::documents(f, Setters::VALUE))
         };
-        let arg_type = quote! {ValType};
-        let documents = Self::documents(f, Setters::VALUE);
 
-        builder_fields[index] = match &f.attrs.validator {
-            Some(v) => quote_spanned! { v.span() =>
-                #ident: Some(
-                    ::builder_pattern::setter::Setter::LazyValidated(
-                        Box::new(move || #v((value)().into()))
-                    )
-                )
-            },
-            None => quote! {
-                #ident: Some(
-                    ::builder_pattern::setter::Setter::Lazy(
-                        Box::new(move || (value)().into())
-                    )
-                )
-            },
-        };
-        let ret_expr_val = quote! {
-            #builder_name {
-                _phantom: ::core::marker::PhantomData,
-                #(#builder_fields),*
+        let value = f.wrap_value(Self::convert(f, quote! { (value)() }, true));
+        let setter = Self::deferred_setter(f, value, false);
+        builder_fields[index] = if maybe {
+            quote! {
+                #ident: match value {
+                    Some(value) => Some(#setter),
+                    None => self.#ident,
+                }
+            }
+        } else {
+            quote! {
+                #ident: Some(#setter)
             }
         };
+        let ret_expr_val = self.builder_expr(builder_fields);
 
-        let validator_option = if f.attrs.validator.is_some() {
+        let validator_option = if f.attrs.validator.is_some()
+            || f.attrs.validator_async.is_some()
+            || f.attrs.conversion.is_some()
+        {
             quote! {::builder_pattern::setter::HavingLazyValidator}
         } else {
             quote! {ValidatorOption}
         };
-
-        let ret_type = quote! {
-            #builder_name <
-                #fn_lifetime,
-                #(#lifetimes,)*
-                #ty_tokens
-                #(#after_generics,)*
-                AsyncFieldMarker,
-                #validator_option
-            >
+        let async_generic = if f.attrs.validator_async.is_some() {
+            quote! {::builder_pattern::setter::AsyncBuilderMarker}
+        } else {
+            quote! {AsyncFieldMarker}
         };
 
-        tokens.extend(quote! {
-            impl <
-                #fn_lifetime,
-                #impl_tokens
-                #(#other_generics,)*
-                AsyncFieldMarker,
-                ValidatorOption
-            > #builder_name <
-                #fn_lifetime,
-                #(#lifetimes,)*
-                #ty_tokens
-                #(#before_generics,)*
-                AsyncFieldMarker,
-                ValidatorOption
-            >
-                #where_clause
-            {
-                #(#documents)*
-                #vis fn #seter_name #arg_type_gen(self, value: #arg_type) -> #ret_type {
-                    #[allow(useless_conversion)]
-                    #ret_expr_val
-                }
-            }
-        });
+        let ret_type = self.builder_type(&after_generics, async_generic, validator_option);
+
+        self.write_fn(
+            tokens,
+            &seter_name,
+            ident.unraw().to_string(),
+            maybe,
+            self.impl_builder(
+                &other_generics,
+                &before_generics,
+                quote! {
+                    #(#documents)*
+                    #[allow(clippy::redundant_closure_call)]
+                    #vis fn #seter_name #arg_type_gen(self, value: #arg_type) -> #ret_type {
+                        #[allow(useless_conversion)]
+                        #ret_expr_val
+                    }
+                },
+            ),
+        );
     }
 
     fn write_async_setter(
@@ -297,90 +1181,129 @@ impl<'a> BuilderFunctions<'a> {
         tokens: &mut TokenStream,
         f: &Field,
         index: usize,
-        builder_fields: &mut Vec<TokenStream>,
+        builder_fields: &mut [TokenStream],
+        maybe: bool,
     ) {
-        let (ident, ty, vis) = (&f.ident, &f.ty, &f.vis);
-        let seter_name = Ident::new(&format!("{}_async", &ident.to_string()), Span::call_site());
-        let builder_name = self.input.builder_name();
-        let where_clause = &self.input.generics.where_clause;
-        let lifetimes = self.input.lifetimes();
+        let (ident, vis) = (&f.ident, &f.vis);
+        let seter_name = self.input.async_setter_name(f);
+        let seter_name = if maybe {
+            Self::maybe_name(&seter_name)
+        } else {
+            seter_name
+        };
         let fn_lifetime = self.input.fn_lifetime();
-        let impl_tokens = self.input.tokenize_impl();
-        let ty_tokens = self.input.tokenize_types();
-        let (other_generics, before_generics, after_generics) = self.get_generics(f, index);
-        let arg_type_gen = if f.attrs.use_into {
-            quote! {<
-                IntoType: Into<#ty>,
-                ReturnType: #fn_lifetime + ::core::future::Future<Output = IntoType>,
-                ValType: #fn_lifetime + ::core::ops::Fn() -> ReturnType
-            >}
+        let (other_generics, before_generics, after_generics) = self.get_generics(&[index]);
+        let (arg_gen, arg_type) = Self::deferred_argument(f);
+        let arg_type_gen = quote! {<
+            #arg_gen
+            ReturnType: #fn_lifetime + ::core::future::Future<Output = #arg_type>,
+            ValType: #fn_lifetime + ::core::ops::Fn() -> ReturnType
+        >};
+        let (arg_type, documents) = if maybe {
+            (quote! {Option<ValType>}, Self::maybe_documents(f))
         } else {
-            quote! {<
-                ReturnType: #fn_lifetime + ::core::future::Future<Output = #ty>,
-                ValType: #fn_lifetime + ::core::ops::Fn() -> ReturnType
-            >}
+            (quote! {ValType}, Self::documents(f, Setters::VALUE))
         };
-        let arg_type = quote! {ValType};
-        let documents = Self::documents(f, Setters::VALUE);
 
-        builder_fields[index] = match &f.attrs.validator {
-            Some(v) => quote_spanned! { v.span() =>
-                #ident: Some(
-                    ::builder_pattern::setter::Setter::AsyncValidated(
-                        Box::new(move || {
-                            Box::pin(async move { #v((value)().await.into()) })
-                        })
-                    )
-                )
-            },
-            None => quote! {
-                #ident: Some(
-                    ::builder_pattern::setter::Setter::Async(
-                        Box::new(move || Box::pin(async move { (value)().await.into() }))
-                    )
-                )
-            },
-        };
-        let ret_expr_val = quote! {
-            #builder_name {
-                _phantom: ::core::marker::PhantomData,
-                #(#builder_fields),*
+        let value = f.wrap_value(Self::convert(f, quote! { (value)().await }, true));
+        let setter = Self::deferred_setter(f, value, true);
+        builder_fields[index] = if maybe {
+            quote! {
+                #ident: match value {
+                    Some(value) => Some(#setter),
+                    None => self.#ident,
+                }
+            }
+        } else {
+            quote! {
+                #ident: Some(#setter)
             }
         };
+        let ret_expr_val = self.builder_expr(builder_fields);
 
-        let validator_option = if f.attrs.validator.is_some() {
+        let validator_option = if f.attrs.validator.is_some()
+            || f.attrs.validator_async.is_some()
+            || f.attrs.conversion.is_some()
+        {
             quote! {::builder_pattern::setter::HavingLazyValidator}
         } else {
             quote! {ValidatorOption}
         };
 
-        let ret_type = quote! {
-            #builder_name <#fn_lifetime, #(#lifetimes,)* #ty_tokens #(#after_generics,)* ::builder_pattern::setter::AsyncBuilderMarker, #validator_option>
+        let ret_type = self.builder_type(
+            &after_generics,
+            quote! {::builder_pattern::setter::AsyncBuilderMarker},
+            validator_option,
+        );
+
+        self.write_fn(
+            tokens,
+            &seter_name,
+            ident.unraw().to_string(),
+            maybe,
+            self.impl_builder(
+                &other_generics,
+                &before_generics,
+                quote! {
+                    #(#documents)*
+                    #[allow(clippy::redundant_closure_call)]
+                    #vis fn #seter_name #arg_type_gen(self, value: #arg_type) -> #ret_type {
+                        #[allow(useless_conversion)]
+                        #ret_expr_val
+                    }
+                },
+            ),
+        );
+    }
+
+    fn write_each_setter(&self, tokens: &mut TokenStream, f: &Field) {
+        let (ident, ty, vis) = (&f.ident, &f.ty, &f.vis);
+        let setter_name = f.attrs.each.as_ref().unwrap();
+        let extend_name = make_ident(&format!("extend_{}", f.name()));
+        self.register(setter_name, f.ident.unraw().to_string());
+        self.register(&extend_name, f.ident.unraw().to_string());
+        let fn_lifetime = self.input.fn_lifetime();
+        let all_generics = self.input.all_generics().collect::<Vec<_>>();
+        let items = crate::field::collection_items(ty).unwrap();
+        let (args, item_ty, item) = match items.as_slice() {
+            [key, value] => (
+                quote! { key: #key, value: #value },
+                quote! { (#key, #value) },
+                quote! { (key, value) },
+            ),
+            [value] => (quote! { item: #value }, quote! { #value }, quote! { item }),
+            _ => unreachable!(),
         };
+        let documents = Self::documents(f, Setters::EACH);
 
-        tokens.extend(quote! {
-            impl <
-                #fn_lifetime,
-                #impl_tokens
-                #(#other_generics,)*
-                AsyncFieldMarker,
-                ValidatorOption
-            > #builder_name <
-                #fn_lifetime,
-                #(#lifetimes,)*
-                #ty_tokens
-                #(#before_generics,)*
-                AsyncFieldMarker,
-                ValidatorOption
-            >
-                #where_clause
-            {
+        // The typestate of the field is not changed.
+        tokens.extend(self.impl_builder(
+            &all_generics,
+            &all_generics,
+            quote! {
                 #(#documents)*
-                #vis fn #seter_name #arg_type_gen(self, value: #arg_type) -> #ret_type {
-                    #[allow(useless_conversion)]
-                    #ret_expr_val
+                #vis fn #setter_name(self, #args) -> Self
+                where
+                    #ty: #fn_lifetime,
+                {
+                    self.#extend_name(::core::iter::once(#item))
                 }
-            }
-        });
+
+                #(#documents)*
+                #vis fn #extend_name<IterType>(mut self, iter: IterType) -> Self
+                where
+                    #ty: #fn_lifetime,
+                    IterType: #fn_lifetime + ::core::iter::IntoIterator<Item = #item_ty>,
+                {
+                    self.#ident = self.#ident.map(|setter| {
+                        setter.map(move |mut collection: #ty| {
+                            collection.extend(iter);
+                            collection
+                        })
+                    });
+                    self
+                }
+            },
+        ));
     }
 }
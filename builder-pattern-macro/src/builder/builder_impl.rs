@@ -1,8 +1,13 @@
-use crate::{attributes::Setters, struct_input::StructInput};
+use crate::{
+    attributes::{BuildFn, Setters},
+    field::Field,
+    struct_input::StructInput,
+};
 
 use core::str::FromStr;
-use proc_macro2::TokenStream;
+use proc_macro2::{Ident, Literal, TokenStream};
 use quote::ToTokens;
+use syn::spanned::Spanned;
 
 pub struct BuilderImpl<'a> {
     pub input: &'a StructInput,
@@ -13,18 +18,21 @@ impl<'a> ToTokens for BuilderImpl<'a> {
         let mut async_fields = vec![];
         let mut sync_fields = vec![];
         self.input.required_fields.iter().for_each(|f| {
-            if !(f.attrs.setters & (Setters::LAZY | Setters::VALUE)).is_empty() {
+            if !(f.attrs.setters & (Setters::LAZY | Setters::VALUE)).is_empty()
+                && f.attrs.validator_async.is_none()
+            {
                 sync_fields.push(f);
             }
-            if !(f.attrs.setters & Setters::ASYNC).is_empty() {
+            if !(f.attrs.setters & Setters::ASYNC).is_empty() || f.attrs.validator_async.is_some() {
                 async_fields.push(f);
             }
         });
         self.input.optional_fields.iter().for_each(|f| {
-            if !(f.attrs.setters & Setters::ASYNC).is_empty() {
+            if !(f.attrs.setters & Setters::ASYNC).is_empty() || f.attrs.validator_async.is_some() {
                 async_fields.push(f);
             }
         });
+        self.write_filled_impls(tokens);
         // All of fields have synchronous setters.
         // The structure can be build synchronously.
         if sync_fields.len() == self.input.required_fields.len() {
@@ -46,28 +54,152 @@ impl<'a> BuilderImpl<'a> {
     /// An iterator for optional fields.
     fn optional_generics(&self) -> impl Iterator<Item = TokenStream> {
         let offset = self.input.required_fields.len() + 1;
-        (0..self.input.optional_fields.len())
-            .into_iter()
-            .map(move |i| {
-                TokenStream::from_str(&format!("TyBuilderPattern{}", i + offset)).unwrap()
-            })
+        (0..self.input.optional_fields.len()).map(move |i| {
+            TokenStream::from_str(&format!("TyBuilderPattern{}", i + offset)).unwrap()
+        })
     }
 
     /// An iterator to describe when the builder has enough types to build the struct.
+    /// Required fields set by presets may be `Preset<T>`, so they are generic.
     fn satified_generics(&'_ self) -> impl '_ + Iterator<Item = TokenStream> {
         self.input
             .required_fields
             .iter()
-            .map(|f| {
-                let ty = &f.ty;
-                quote! {#ty}
+            .enumerate()
+            .map(move |(i, f)| {
+                if f.attrs.preset {
+                    TokenStream::from_str(&format!("TyBuilderPattern{}", i + 1)).unwrap()
+                } else {
+                    self.input.set_slot(f)
+                }
             })
             .chain(self.optional_generics())
     }
 
-    fn write_builder(&self, tokens: &mut TokenStream, is_async: bool) {
+    /// `Filled` of the `index`th field, which is implemented for the states of the set field.
+    fn filled_trait(&self, index: usize) -> TokenStream {
         let ident = &self.input.ident;
-        let vis = &self.input.vis;
+        let lifetimes = self.input.lifetimes();
+        let ty_tokens = self.input.tokenize_types();
+        let index = Literal::usize_unsuffixed(index);
+        quote! {
+            ::builder_pattern::setter::Filled<#ident <#(#lifetimes,)* #ty_tokens>, #index>
+        }
+    }
+
+    /// An iterator for generics of required fields set by presets, which are bounded by `Filled`.
+    fn filled_generics(&'_ self) -> impl '_ + Iterator<Item = TokenStream> {
+        self.input
+            .required_fields
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| f.attrs.preset)
+            .map(move |(i, _)| {
+                let generic = TokenStream::from_str(&format!("TyBuilderPattern{}", i + 1)).unwrap();
+                let filled = self.filled_trait(i);
+                quote! { #generic: #filled }
+            })
+    }
+
+    /// `Filled` for the types of required fields set by presets.
+    /// `Set<T>` is used for the set state if the type may be `()`, which implements it already.
+    fn write_filled_impls(&self, tokens: &mut TokenStream) {
+        let where_clause = &self.input.generics.where_clause;
+        let impl_tokens = self.input.tokenize_impl();
+        self.input
+            .required_fields
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| f.attrs.preset && !self.input.may_be_unit(&f.ty))
+            .for_each(|(i, f)| {
+                let ty = &f.ty;
+                let filled = self.filled_trait(i);
+                tokens.extend(quote! {
+                    impl <#impl_tokens> #filled for #ty #where_clause {}
+                });
+            });
+    }
+
+    /// Name of the structure made by the builder.
+    /// It is the structure of resolved fields with `build_fn(skip)`.
+    fn output_name(&self) -> Ident {
+        if self.input.attrs.build_fn.skip {
+            self.input.parts_name()
+        } else {
+            self.input.ident.clone()
+        }
+    }
+
+    /// An expression to make the structure from the resolved fields.
+    /// `finalize` and `validate` of the structure are applied if they are given.
+    fn struct_expr(&self, struct_init_args: &[TokenStream], wrap_ok: bool) -> TokenStream {
+        let ident = self.output_name();
+        let mut expr = quote! {
+            #ident {
+                #(#struct_init_args),*
+            }
+        };
+        if let Some(f) = &self.input.attrs.finalize {
+            expr = quote_spanned! { f.span() => (#f)(#expr) };
+        }
+        match &self.input.attrs.validate {
+            Some(v) => {
+                let lifetimes = self.input.lifetimes();
+                let ty_tokens = self.input.tokenize_types();
+                let ty: syn::Type = syn::parse_quote! { #ident <#(#lifetimes,)* #ty_tokens> };
+                let call = v.call(&ty, expr);
+                quote_spanned! { v.span() =>
+                    match #call {
+                        Ok(v) => Ok(v),
+                        Err(e) => Err(::core::convert::Into::into(e)),
+                    }
+                }
+            }
+            None if wrap_ok => quote! { Ok(#expr) },
+            None => expr,
+        }
+    }
+
+    /// Error type of the build function.
+    fn error_type(&self) -> TokenStream {
+        match &self.input.attrs.error {
+            Some(ty) => ty.to_token_stream(),
+            None => quote! { &'static str },
+        }
+    }
+
+    /// Whether the `build` function always returns `Result`.
+    fn always_fallible(&self) -> bool {
+        self.input.attrs.validate.is_some()
+            || self
+                .input
+                .required_fields
+                .iter()
+                .chain(self.input.optional_fields.iter())
+                .any(|f| f.attrs.validator_context.is_some())
+    }
+
+    /// An expression to take the setter of the field.
+    /// A lazy default taking the context is evaluated here.
+    fn take_setter(&self, f: &Field) -> TokenStream {
+        let ident = &f.ident;
+        match &f.attrs.default {
+            Some((expr, _)) if f.attrs.default_with_context => {
+                let value = f.transformed(
+                    quote_spanned! { expr.span() => (#expr)(builder_context) },
+                    true,
+                );
+                quote! {
+                    self.#ident.unwrap_or_else(|| ::builder_pattern::setter::Setter::Value(#value))
+                }
+            }
+            _ => quote! { self.#ident.unwrap() },
+        }
+    }
+
+    fn write_builder(&self, tokens: &mut TokenStream, is_async: bool) {
+        let ident = self.output_name();
+        let vis = self.input.build_fn_vis();
         let builder_name = self.input.builder_name();
         let where_clause = &self.input.generics.where_clause;
         let lifetimes = self.input.lifetimes();
@@ -75,7 +207,10 @@ impl<'a> BuilderImpl<'a> {
         let fn_lifetime = self.input.fn_lifetime();
 
         let impl_tokens = self.input.tokenize_impl();
-        let optional_generics = self.optional_generics().collect::<Vec<_>>();
+        let optional_generics = self
+            .filled_generics()
+            .chain(self.optional_generics())
+            .collect::<Vec<_>>();
         let satisfied_generics = self.satified_generics().collect::<Vec<_>>();
         let ty_tokens = self.input.tokenize_types();
 
@@ -83,16 +218,24 @@ impl<'a> BuilderImpl<'a> {
         let mut validated_init_fields = vec![];
         let mut init_fields = vec![];
         let mut no_lazy_validation_fields = vec![];
+        let mut context_validated_fields = vec![];
         self.input
             .required_fields
             .iter()
             .chain(self.input.optional_fields.iter())
             .for_each(|f| {
                 let ident = &f.ident;
+                let setter = self.take_setter(f);
                 struct_init_args.push(ident.to_token_stream());
-                if f.attrs.validator.is_some()
-                    && !(f.attrs.setters & (Setters::LAZY | Setters::ASYNC)).is_empty()
-                {
+                if let Some(v) = &f.attrs.validator_context {
+                    context_validated_fields.push(quote_spanned! { v.span() =>
+                        let #ident = match (#v)(#ident, builder_context) {
+                            Ok(v) => v,
+                            Err(e) => return Err(::core::convert::Into::into(e)),
+                        };
+                    });
+                }
+                if f.attrs.lazy_validated() {
                     let async_case = if is_async {
                         quote! {
                             ::builder_pattern::setter::Setter::Async(f) => Ok(f().await),
@@ -102,14 +245,14 @@ impl<'a> BuilderImpl<'a> {
                         quote! {_ => unimplemented!()}
                     };
                     validated_init_fields.push(quote! {
-                        let #ident = match match self.#ident.unwrap() {
+                        let #ident = match match #setter {
                             ::builder_pattern::setter::Setter::Value(v) => Ok(v),
                             ::builder_pattern::setter::Setter::Lazy(f) => Ok(f()),
                             ::builder_pattern::setter::Setter::LazyValidated(f) => f(),
                             #async_case
                         } {
                             Ok(v) => v,
-                            Err(e) => return Err(e),
+                            Err(e) => return Err(::core::convert::Into::into(e)),
                         };
                     });
                 } else {
@@ -122,7 +265,7 @@ impl<'a> BuilderImpl<'a> {
                         quote! {_ => unimplemented!()}
                     };
                     init_fields.push(quote! {
-                        let #ident = match self.#ident.unwrap() {
+                        let #ident = match #setter {
                             ::builder_pattern::setter::Setter::Value(v) => v,
                             ::builder_pattern::setter::Setter::Lazy(f) => f(),
                             #async_case
@@ -138,13 +281,32 @@ impl<'a> BuilderImpl<'a> {
                     quote! {_ => unimplemented!()}
                 };
                 no_lazy_validation_fields.push(quote! {
-                    let #ident = match self.#ident.unwrap() {
+                    let #ident = match #setter {
                         ::builder_pattern::setter::Setter::Value(v) => v,
                         ::builder_pattern::setter::Setter::Lazy(f) => f(),
                         #async_case
                     };
                 });
             });
+        let error_type = self.error_type();
+        let ret_type = if self.always_fallible() {
+            quote! { Result<#ident <#(#lifetimes,)* #ty_tokens>, #error_type> }
+        } else {
+            quote! { #ident <#(#lifetimes,)* #ty_tokens> }
+        };
+        let struct_expr = self.struct_expr(&struct_init_args, self.always_fallible());
+        let validated_struct_expr = self.struct_expr(&struct_init_args, true);
+        let (build_name, context_arg) = match &self.input.attrs.context {
+            Some(ty) => (quote! {build_with}, Some(quote! {, builder_context: &#ty})),
+            None => (quote! {build}, None),
+        };
+        let build_name = match &self.input.attrs.build_fn {
+            BuildFn {
+                name: Some(name), ..
+            } => name.to_token_stream(),
+            BuildFn { skip: true, .. } => quote! {into_parts},
+            _ => build_name,
+        };
         let (kw_async, async_generic) = if is_async {
             (
                 Some(quote! {async}),
@@ -158,12 +320,16 @@ impl<'a> BuilderImpl<'a> {
             <#fn_lifetime, #(#lifetimes,)* #ty_tokens #(#satisfied_generics),*, #async_generic, ()>
             #where_clause
             {
-                #[allow(dead_code)]
-                #vis #kw_async fn build(self) -> #ident <#(#lifetimes,)* #ty_tokens> {
+                #[allow(
+                    dead_code,
+                    clippy::redundant_closure_call,
+                    clippy::question_mark,
+                    clippy::useless_conversion
+                )]
+                #vis #kw_async fn #build_name(self #context_arg) -> #ret_type {
                     #(#no_lazy_validation_fields)*
-                    #ident {
-                        #(#struct_init_args),*
-                    }
+                    #(#context_validated_fields)*
+                    #struct_expr
                 }
             }
         });
@@ -180,8 +346,9 @@ impl<'a> BuilderImpl<'a> {
                     None => Setters::empty(),
                 };
                 let setters = f.attrs.setters | default_setters;
-                if f.attrs.validator.is_some()
-                    && !(setters & (Setters::LAZY | Setters::ASYNC)).is_empty()
+                if f.attrs.lazy_validated()
+                    || (f.attrs.validator.is_some()
+                        && !(setters & (Setters::LAZY | Setters::ASYNC)).is_empty())
                 {
                     having_lazy_validator = true;
                 }
@@ -198,15 +365,17 @@ impl<'a> BuilderImpl<'a> {
                 >
                     #where_clause
                 {
-                    #[allow(dead_code)]
-                    #vis #kw_async fn build(self) -> Result<#ident <#(#lifetimes,)* #ty_tokens>, &'static str> {
+                    #[allow(
+                    dead_code,
+                    clippy::redundant_closure_call,
+                    clippy::question_mark,
+                    clippy::useless_conversion
+                )]
+                    #vis #kw_async fn #build_name(self #context_arg) -> Result<#ident <#(#lifetimes,)* #ty_tokens>, #error_type> {
                         #(#init_fields)*
                         #(#validated_init_fields)*
-                        Ok(
-                            #ident {
-                                #(#struct_init_args),*
-                            }
-                        )
+                        #(#context_validated_fields)*
+                        #validated_struct_expr
                     }
                 }
             });
@@ -1,9 +1,11 @@
-use super::attributes::FieldAttributes;
+use super::attributes::{Conversion, FieldAttributes};
 
 use core::cmp::Ordering;
-use proc_macro2::Ident;
+use proc_macro2::{Ident, Span, TokenStream};
 use quote::ToTokens;
-use syn::{Attribute, Type, Visibility};
+use syn::{
+    ext::IdentExt, Attribute, GenericArgument, PathArguments, Type, TypeParamBound, Visibility,
+};
 
 pub struct Field {
     pub vis: Visibility,
@@ -13,6 +15,15 @@ pub struct Field {
 }
 
 impl Field {
+    /// Name of the field used in names of the functions.
+    /// Raw identifiers are used without `r#`.
+    pub fn name(&self) -> String {
+        match &self.attrs.rename {
+            Some(name) => name.clone(),
+            None => self.ident.unraw().to_string(),
+        }
+    }
+
     pub fn documents(&self) -> Vec<Attribute> {
         self.attrs
             .documents
@@ -22,13 +33,198 @@ impl Field {
             .collect()
     }
 
-    pub fn type_documents(&self) -> String {
-        let ty_token = self.ty.clone().into_token_stream();
-        if self.attrs.use_into {
-            format!("Into<{}>", ty_token)
+    /// An expression applying `transform`s of the field to `value`.
+    /// If `is_default` is true, only the transforms for the default value are applied.
+    pub fn transformed(&self, value: TokenStream, is_default: bool) -> TokenStream {
+        let ty = &self.ty;
+        let transforms = self
+            .attrs
+            .transforms
+            .iter()
+            .filter(|(_, default)| !is_default || *default)
+            .map(|(t, _)| t)
+            .collect::<Vec<_>>();
+        if transforms.is_empty() {
+            return value;
+        }
+        quote! {
+            {
+                let value: #ty = #value;
+                #(
+                    let value: #ty = {
+                        let transform: fn(#ty) -> #ty = #transforms;
+                        transform(value)
+                    };
+                )*
+                value
+            }
+        }
+    }
+
+    /// The type which setters take. It is `T` for the field of `Option<T>` with `strip_option`,
+    /// and `T` for the field of a smart pointer of `T` with `wrap`.
+    pub fn setter_ty(&self) -> &Type {
+        let ty = self.stripped_ty();
+        match wrapper(ty) {
+            Some((_, inner)) if self.attrs.wrap => inner,
+            _ => ty,
+        }
+    }
+
+    /// The type of the field without `Option` if it has `strip_option`.
+    fn stripped_ty(&self) -> &Type {
+        match option_inner(&self.ty) {
+            Some(ty) if self.attrs.strip_option => ty,
+            _ => &self.ty,
+        }
+    }
+
+    /// Bounds of the trait object if setters take it as a generic type.
+    pub fn trait_object_bounds(&self) -> Option<TokenStream> {
+        match self.setter_ty() {
+            Type::TraitObject(t) if self.attrs.wrap => {
+                let bounds = &t.bounds;
+                if bounds
+                    .iter()
+                    .any(|b| matches!(b, TypeParamBound::Lifetime(_)))
+                {
+                    Some(quote! { #bounds })
+                } else {
+                    Some(quote! { #bounds + 'static })
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Wrap a value of `setter_ty` into the type of the field.
+    pub fn wrap_value(&self, value: TokenStream) -> TokenStream {
+        let value = match wrapper(self.stripped_ty()) {
+            Some((constructor, _)) if self.attrs.wrap => {
+                let ty = self.stripped_ty();
+                // Trait objects are coerced here.
+                quote! {
+                    {
+                        let value: #ty = #constructor(#value);
+                        value
+                    }
+                }
+            }
+            _ => value,
+        };
+        if self.attrs.strip_option {
+            quote! { Some(#value) }
         } else {
-            ty_token.to_string()
+            value
+        }
+    }
+
+    pub fn type_documents(&self) -> String {
+        if let Some(bounds) = self.trait_object_bounds() {
+            return format!("impl {}", bounds);
+        }
+        let ty_token = self.setter_ty().clone().into_token_stream();
+        match self.attrs.conversion {
+            Some(Conversion::TryInto) => format!("TryInto<{}>", ty_token),
+            Some(Conversion::Parse) => format!("&str -> {}", ty_token),
+            None if self.attrs.use_into => format!("Into<{}>", ty_token),
+            None => ty_token.to_string(),
+        }
+    }
+}
+
+/// The type `T` if the given type is `Option<T>`.
+pub fn option_inner(ty: &Type) -> Option<&Type> {
+    match type_arguments(ty, &["Option"])?.as_slice() {
+        [ty] => Some(ty),
+        _ => None,
+    }
+}
+
+/// The constructor and the type argument if the given type is `Box`, `Arc`, `Rc` or `Cow`.
+pub fn wrapper(ty: &Type) -> Option<(TokenStream, &Type)> {
+    let constructors = [
+        ("Box", quote! { ::std::boxed::Box::new }),
+        ("Arc", quote! { ::std::sync::Arc::new }),
+        ("Rc", quote! { ::std::rc::Rc::new }),
+        ("Cow", quote! { ::std::borrow::Cow::Owned }),
+    ];
+    constructors.iter().find_map(|(name, constructor)| {
+        match type_arguments(ty, &[name])?.as_slice() {
+            [ty] => Some((constructor.clone(), *ty)),
+            _ => None,
         }
+    })
+}
+
+/// Whether the given type is a trait object of `Fn`, `FnMut` or `FnOnce`.
+pub fn is_fn_trait_object(ty: &Type) -> bool {
+    match ty {
+        Type::TraitObject(t) => t.bounds.iter().any(|b| match b {
+            TypeParamBound::Trait(t) => t
+                .path
+                .segments
+                .last()
+                .is_some_and(|s| s.ident == "Fn" || s.ident == "FnMut" || s.ident == "FnOnce"),
+            _ => false,
+        }),
+        _ => false,
+    }
+}
+
+/// Whether the given type is one of well-known unsized types except trait objects.
+pub fn is_unsized(ty: &Type) -> bool {
+    match ty {
+        Type::Slice(_) => true,
+        Type::Path(p) if p.qself.is_none() => ["str", "Path", "OsStr", "CStr"]
+            .iter()
+            .any(|name| p.path.is_ident(name)),
+        _ => false,
+    }
+}
+
+/// Whether the given type is `bool`.
+pub fn is_bool(ty: &Type) -> bool {
+    match ty {
+        Type::Path(p) if p.qself.is_none() => p.path.is_ident("bool"),
+        _ => false,
+    }
+}
+
+/// Types of items of the collection.
+/// It is `[T]` for sequences and sets, or `[K, V]` for maps.
+pub fn collection_items(ty: &Type) -> Option<Vec<&Type>> {
+    if let Some(args) = type_arguments(ty, &["Vec", "VecDeque", "HashSet", "BTreeSet"]) {
+        args.into_iter().next().map(|ty| vec![ty])
+    } else {
+        let args = type_arguments(ty, &["HashMap", "BTreeMap"])?;
+        if args.len() < 2 {
+            return None;
+        }
+        Some(args.into_iter().take(2).collect())
+    }
+}
+
+/// Type arguments of the given type if its name is one of `names`.
+fn type_arguments<'a>(ty: &'a Type, names: &[&str]) -> Option<Vec<&'a Type>> {
+    let segment = match ty {
+        Type::Path(p) if p.qself.is_none() => p.path.segments.last()?,
+        _ => return None,
+    };
+    if !names.iter().any(|name| segment.ident == name) {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) => Some(
+            args.args
+                .iter()
+                .filter_map(|arg| match arg {
+                    GenericArgument::Type(ty) => Some(ty),
+                    _ => None,
+                })
+                .collect(),
+        ),
+        _ => None,
     }
 }
 
@@ -40,7 +236,7 @@ impl Ord for Field {
 
 impl PartialOrd for Field {
     fn partial_cmp(&self, other: &Field) -> Option<Ordering> {
-        Some(self.ident.cmp(&other.ident))
+        Some(self.cmp(other))
     }
 }
 
@@ -51,3 +247,11 @@ impl PartialEq for Field {
         self.ident.eq(&other.ident)
     }
 }
+
+/// Make an identifier from the name. Keywords are made raw identifiers.
+pub fn make_ident(name: &str) -> Ident {
+    match syn::parse_str::<Ident>(name) {
+        Ok(ident) => ident,
+        Err(_) => Ident::new_raw(name, Span::call_site()),
+    }
+}
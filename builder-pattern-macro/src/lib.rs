@@ -6,6 +6,7 @@ mod builder;
 mod field;
 mod struct_impl;
 mod struct_input;
+mod validator;
 
 use struct_input::StructInput;
 
@@ -23,14 +24,18 @@ extern crate proc_macro2;
 #[proc_macro_derive(
     Builder,
     attributes(
+        builder,
         default,
         default_async,
         default_lazy,
         hidden,
         into,
         public,
+        required,
         setter,
-        validator
+        transform,
+        validator,
+        validator_async
     )
 )]
 pub fn derive_builder(input: TokenStream) -> TokenStream {
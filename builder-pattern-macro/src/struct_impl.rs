@@ -1,12 +1,17 @@
-use crate::{attributes::Setters, struct_input::StructInput};
+use crate::{
+    attributes::{Constructor, FieldVisibility, Preset, Setters},
+    builder::builder_functions::BuilderFunctions,
+    field::Field,
+    struct_input::StructInput,
+};
 
 use core::str::FromStr;
-use proc_macro2::TokenStream;
+use proc_macro2::{Ident, TokenStream};
 use quote::ToTokens;
-use syn::{parse_quote, spanned::Spanned, Attribute};
+use syn::{parse_quote, spanned::Spanned, Attribute, Expr};
 
 /// Implementation for the given structure.
-/// It creates a `new` function.
+/// It creates a constructor function, which is `new` by default, and additional constructors.
 pub struct StructImpl<'a> {
     pub input: &'a StructInput,
 }
@@ -14,9 +19,10 @@ pub struct StructImpl<'a> {
 impl<'a> ToTokens for StructImpl<'a> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let ident = &self.input.ident;
-        let vis = &self.input.vis;
+        let vis = self.input.builder_vis();
         let where_clause = &self.input.generics.where_clause;
         let builder_name = self.input.builder_name();
+        let constructor_name = self.input.constructor_name();
 
         let lifetimes = self.input.lifetimes();
         let impl_tokens = self.input.tokenize_impl();
@@ -32,7 +38,7 @@ impl<'a> ToTokens for StructImpl<'a> {
             impl <#impl_tokens> #ident <#(#lifetimes,)* #ty_tokens> #where_clause {
                 #(#docs)*
                 #[allow(clippy::new_ret_no_self)]
-                #vis fn new<#fn_lifetime>() -> #builder_name<
+                #vis fn #constructor_name<#fn_lifetime>() -> #builder_name<
                     #fn_lifetime,
                     #(#lifetimes,)*
                     #ty_tokens
@@ -48,6 +54,60 @@ impl<'a> ToTokens for StructImpl<'a> {
                 }
             }
         });
+
+        self.input
+            .attrs
+            .constructors
+            .iter()
+            .for_each(|c| self.write_constructor(tokens, c));
+        self.input
+            .attrs
+            .presets
+            .iter()
+            .for_each(|p| self.write_preset(tokens, p));
+    }
+}
+
+/// An initialize argument of the builder for the optional field.
+/// It is filled with given value via `default` attribute.
+pub fn default_init_arg(f: &Field) -> TokenStream {
+    if let (ident, Some((expr, setters))) = (&f.ident, &f.attrs.default.as_ref()) {
+        if f.attrs.default_with_context {
+            // It is evaluated with the context when the structure is built.
+            return quote! {
+                #ident: None
+            };
+        }
+        match *setters {
+            Setters::VALUE => {
+                let value = f.transformed(quote_spanned! { expr.span() => #expr }, true);
+                quote_spanned! { expr.span() =>
+                    #ident: Some(::builder_pattern::setter::Setter::Value(#value))
+                }
+            }
+            Setters::LAZY if f.attrs.transforms.iter().any(|(_, d)| *d) => {
+                let value = f.transformed(quote_spanned! { expr.span() => (#expr)() }, true);
+                quote_spanned! { expr.span() =>
+                    #ident: Some(
+                        ::builder_pattern::setter::Setter::Lazy(
+                            Box::new(move || #value)
+                        )
+                    )
+                }
+            }
+            Setters::LAZY => {
+                quote_spanned! { expr.span() =>
+                    #ident: Some(
+                        ::builder_pattern::setter::Setter::Lazy(
+                            Box::new(#expr)
+                        )
+                    )
+                }
+            }
+            _ => unimplemented!(),
+        }
+    } else {
+        unimplemented!()
     }
 }
 
@@ -56,10 +116,149 @@ impl<'a> StructImpl<'a> {
         StructImpl { input }
     }
 
+    /// A constructor creating the builder whose fields are set by the arguments.
+    fn write_constructor(&self, tokens: &mut TokenStream, constructor: &Constructor) {
+        let values = constructor.args.iter().map(|arg| (arg, None)).collect();
+        let doc = format!(
+            " # {}\n Creating a builder with fields set.",
+            constructor.name
+        );
+        self.write_preset_fn(tokens, &constructor.name, values, doc);
+    }
+
+    /// A function creating the builder whose fields are set by the given values.
+    fn write_preset(&self, tokens: &mut TokenStream, preset: &Preset) {
+        let values = preset
+            .values
+            .iter()
+            .map(|(ident, expr)| (ident, Some(expr)))
+            .collect();
+        let doc = format!(
+            " # {}\n Creating a builder with the preset. The fields can be overridden.",
+            preset.name
+        );
+        self.write_preset_fn(tokens, &preset.name, values, doc);
+    }
+
+    /// Fields are set by the expressions, or the arguments if the expressions are not given.
+    fn write_preset_fn(
+        &self,
+        tokens: &mut TokenStream,
+        name: &Ident,
+        values: Vec<(&Ident, Option<&Expr>)>,
+        doc: String,
+    ) {
+        let ident = &self.input.ident;
+        let vis = self.input.builder_vis();
+        let where_clause = &self.input.generics.where_clause;
+        let builder_name = self.input.builder_name();
+        let lifetimes = self.input.lifetimes();
+        let impl_tokens = self.input.tokenize_impl();
+        let ty_tokens = self.input.tokenize_types();
+        let fn_lifetime = self.input.fn_lifetime();
+
+        let fields = self
+            .input
+            .required_fields
+            .iter()
+            .chain(self.input.optional_fields.iter())
+            .collect::<Vec<_>>();
+        let mut slots = self.empty_generics().collect::<Vec<_>>();
+        let mut generics = vec![];
+        let mut args = vec![];
+        let mut field_values = vec![];
+        let mut docs = vec![doc];
+        values.into_iter().for_each(|(field, expr)| {
+            let (index, f) = fields
+                .iter()
+                .enumerate()
+                .find(|(_, f)| &f.ident == field)
+                .unwrap_or_else(|| unimplemented!("Unknown field `{}`.", field));
+            if matches!(f.attrs.vis, FieldVisibility::Hidden) {
+                unimplemented!("Hidden field `{}` can't be set.", field)
+            }
+            // Fields set by presets can be overridden, so they have their own state.
+            slots[index] = match expr {
+                Some(_) => self.input.preset_slot(f),
+                None => self.input.set_slot(f),
+            };
+            let value = match expr {
+                Some(expr) => {
+                    docs.push(format!(" - `{}`: `{}`", field, expr.to_token_stream()));
+                    // Literals can't be inferred through `Into`.
+                    if f.attrs.use_into || f.attrs.conversion.is_some() {
+                        BuilderFunctions::convert(
+                            f,
+                            quote_spanned! { expr.span() => (#expr) },
+                            false,
+                        )
+                    } else {
+                        quote_spanned! { expr.span() => #expr }
+                    }
+                }
+                None => {
+                    let (mut arg_generics, arg_type) =
+                        BuilderFunctions::value_argument(f, &(index + 1).to_string());
+                    generics.append(&mut arg_generics);
+                    args.push(quote! { #field: #arg_type });
+                    docs.push(format!(" - `{}`: `{}`", field, f.type_documents()));
+                    BuilderFunctions::convert(f, quote! {#field}, false)
+                }
+            };
+            field_values.push((index, *f, f.wrap_value(value)));
+        });
+
+        let mut builder_fields = self.builder_init_args();
+        let (statements, fallible, validate_async) =
+            BuilderFunctions::value_statements(field_values, false, &mut builder_fields);
+        let markers = if validate_async {
+            quote! {
+                ::builder_pattern::setter::AsyncBuilderMarker,
+                ::builder_pattern::setter::HavingLazyValidator
+            }
+        } else {
+            quote! {(), ()}
+        };
+        let builder_type = quote! {
+            #builder_name <
+                #fn_lifetime,
+                #(#lifetimes,)*
+                #ty_tokens
+                #(#slots,)*
+                #markers
+            >
+        };
+        let ret_builder = quote! {
+            #builder_name {
+                _phantom: ::core::marker::PhantomData,
+                #(#builder_fields),*
+            }
+        };
+        let (ret_type, ret_expr) = if fallible {
+            (
+                quote! { Result<#builder_type, String> },
+                quote! { Ok(#ret_builder) },
+            )
+        } else {
+            (builder_type, ret_builder)
+        };
+        let doc = docs.join("\n");
+
+        tokens.extend(quote! {
+            impl <#impl_tokens> #ident <#(#lifetimes,)* #ty_tokens> #where_clause {
+                #[doc=#doc]
+                #[allow(clippy::redundant_closure_call)]
+                #vis fn #name<#fn_lifetime, #(#generics),*>(#(#args),*) -> #ret_type {
+                    #(#statements)*
+                    #ret_expr
+                }
+            }
+        });
+    }
+
     /// An iterator to describe initial state of builder.
     fn empty_generics(&self) -> impl Iterator<Item = TokenStream> {
         (0..(self.input.required_fields.len() + self.input.optional_fields.len()))
-            .into_iter()
             .map(|_| TokenStream::from_str("()").unwrap())
     }
 
@@ -76,27 +275,7 @@ impl<'a> StructImpl<'a> {
                     #ident: None
                 }
             })
-            .chain(self.input.optional_fields.iter().map(|f| {
-                if let (ident, Some((expr, setters))) = (&f.ident, &f.attrs.default.as_ref()) {
-                    match *setters {
-                        Setters::VALUE => quote_spanned! { expr.span() =>
-                            #ident: Some(::builder_pattern::setter::Setter::Value(#expr))
-                        },
-                        Setters::LAZY => {
-                            quote_spanned! { expr.span() =>
-                                #ident: Some(
-                                    ::builder_pattern::setter::Setter::Lazy(
-                                        Box::new(#expr)
-                                    )
-                                )
-                            }
-                        }
-                        _ => unimplemented!(),
-                    }
-                } else {
-                    unimplemented!()
-                }
-            }))
+            .chain(self.input.optional_fields.iter().map(default_init_arg))
             .collect::<Vec<_>>();
         v
     }
@@ -1,24 +1,28 @@
-use crate::attributes::{FieldAttributes, FieldVisibility};
+use crate::attributes::{FieldAttributes, FieldVisibility, Setters, StructAttributes};
 use crate::builder::{
     builder_decl::BuilderDecl, builder_functions::BuilderFunctions, builder_impl::BuilderImpl,
 };
-use crate::field::Field;
+use crate::field::{
+    collection_items, is_bool, is_fn_trait_object, is_unsized, make_ident, option_inner, wrapper,
+    Field,
+};
 use crate::struct_impl::StructImpl;
 
 use core::str::FromStr;
-use proc_macro2::{Ident, Span, TokenStream};
+use proc_macro2::{Ident, Span, TokenStream, TokenTree};
 use quote::{ToTokens, TokenStreamExt};
 use syn::{
+    ext::IdentExt,
     parse::{Parse, ParseStream, Result},
-    AttrStyle, Attribute, Data, DeriveInput, Fields, GenericParam, Generics, Lifetime, Token,
-    VisPublic, Visibility,
+    parse_quote, AttrStyle, Data, DeriveInput, Fields, GenericParam, Generics, Lifetime, Token,
+    Type, VisPublic, Visibility,
 };
 
 pub struct StructInput {
     pub vis: Visibility,
     pub ident: Ident,
     pub generics: Generics,
-    pub attrs: Vec<Attribute>,
+    pub attrs: StructAttributes,
     pub required_fields: Vec<Field>,
     pub optional_fields: Vec<Field>,
 }
@@ -31,9 +35,9 @@ impl Parse for StructInput {
         // Name of the structure.
         let ident = input.ident;
         // Generics of the structure.
-        let generics = input.generics;
+        let mut generics = input.generics;
         // Attributes of the structure.
-        let attrs = input.attrs;
+        let attrs = StructAttributes::from(input.attrs.as_slice());
 
         // Fields of the structure.
         let fields = if let Data::Struct(d) = input.data {
@@ -49,18 +53,97 @@ impl Parse for StructInput {
         let mut optional_fields: Vec<Field> = Vec::new();
         let mut required_fields: Vec<Field> = Vec::new();
         for f in fields.named.into_iter() {
-            let attrs: FieldAttributes = f.attrs.into();
+            let mut field_attrs: FieldAttributes = f.attrs.into();
+            field_attrs.overridable |= attrs.overridable;
+            field_attrs.getter |= attrs.getters;
+            field_attrs.map |= attrs.maps;
+            let field_ident = f.ident.as_ref();
+            field_attrs.preset = attrs
+                .presets
+                .iter()
+                .any(|p| p.values.iter().any(|(ident, _)| field_ident == Some(ident)));
+            if option_inner(&f.ty).is_some() {
+                field_attrs.strip_option |= attrs.strip_option;
+            } else if field_attrs.strip_option {
+                unimplemented!("`strip_option` requires the field of `Option<T>`.")
+            }
+            // Fields with `strip_option` are `None` by default.
+            if field_attrs.strip_option && field_attrs.default.is_none() && !field_attrs.required {
+                field_attrs.default = Some((parse_quote!(None), Setters::VALUE));
+            }
+            if field_attrs.wrap {
+                let ty = match option_inner(&f.ty) {
+                    Some(ty) if field_attrs.strip_option => ty,
+                    _ => &f.ty,
+                };
+                match wrapper(ty) {
+                    // Unsized values can't be given, so they are converted into the pointer.
+                    Some((_, inner)) if is_unsized(inner) => {
+                        field_attrs.wrap = false;
+                        field_attrs.use_into = true;
+                    }
+                    Some((_, Type::TraitObject(_))) if field_attrs.use_into => {
+                        unimplemented!("`into` can't be used for trait objects.")
+                    }
+                    Some((_, inner)) if field_attrs.closure && !is_fn_trait_object(inner) => {
+                        unimplemented!("`closure` requires the field of a boxed function.")
+                    }
+                    Some(_) => {}
+                    None => {
+                        unimplemented!("`wrap` requires the field of `Box`, `Arc`, `Rc` or `Cow`.")
+                    }
+                }
+            }
+            // Collections with `each` setter are empty by default.
+            if field_attrs.each.is_some() {
+                if collection_items(&f.ty).is_none() {
+                    unimplemented!("`each` setter requires the field of a collection.")
+                }
+                if field_attrs.default.is_none() {
+                    field_attrs.default = Some((parse_quote!(Default::default()), Setters::VALUE));
+                }
+            }
+            // Flags are `false` by default.
+            if !(field_attrs.setters & Setters::FLAG).is_empty() {
+                if !is_bool(&f.ty) {
+                    unimplemented!("`flag` setter requires the field of `bool`.")
+                }
+                if field_attrs.default.is_none() && !field_attrs.required {
+                    field_attrs.default = Some((parse_quote!(false), Setters::VALUE));
+                }
+            }
+            if attrs.default_all && field_attrs.default.is_none() && !field_attrs.required {
+                field_attrs.default = Some((parse_quote!(Default::default()), Setters::VALUE));
+            }
+            // Generic fields need to implement `Default` to be `Default::default()`.
+            let is_default_fn = field_attrs.default.as_ref().is_some_and(|(expr, _)| {
+                expr.to_token_stream().to_string() == quote!(Default::default()).to_string()
+            });
+            if is_default_fn && uses_type_params(&generics, &f.ty) {
+                let ty = &f.ty;
+                generics
+                    .make_where_clause()
+                    .predicates
+                    .push(parse_quote!(#ty: ::core::default::Default));
+            }
+            // Hidden fields can't be set, so they need default values including the implicit ones.
+            if matches!(field_attrs.vis, FieldVisibility::Hidden) && field_attrs.default.is_none() {
+                unimplemented!("`hidden` attribute requires `default` attribute.")
+            }
+            let attrs = field_attrs;
             let fields = if attrs.default.is_some() {
                 &mut optional_fields
             } else {
                 &mut required_fields
             };
             fields.push(Field {
-                vis: if attrs.vis == FieldVisibility::Public {
-                    let v = <Token![pub]>::default();
-                    Visibility::Public(VisPublic { pub_token: v })
-                } else {
-                    f.vis
+                vis: match &attrs.vis {
+                    FieldVisibility::Public => {
+                        let v = <Token![pub]>::default();
+                        Visibility::Public(VisPublic { pub_token: v })
+                    }
+                    FieldVisibility::Custom(vis) => vis.clone(),
+                    _ => f.vis,
                 },
                 ident: f
                     .ident
@@ -69,6 +152,14 @@ impl Parse for StructInput {
                 attrs,
             });
         }
+        if attrs.context.is_none()
+            && optional_fields
+                .iter()
+                .chain(required_fields.iter())
+                .any(|f| f.attrs.default_with_context || f.attrs.validator_context.is_some())
+        {
+            unimplemented!("`with_context` requires `context` option of the structure.")
+        }
         // Sort by ident.
         optional_fields.sort();
         required_fields.sort();
@@ -108,9 +199,87 @@ impl StructInput {
         self.required_fields.len() + self.optional_fields.len()
     }
 
+    /// Whether the type may be `()`, which can't be distinguished from the unset state.
+    /// It is true for `()` and types depending on type parameters.
+    pub fn may_be_unit(&self, ty: &Type) -> bool {
+        matches!(ty, Type::Tuple(t) if t.elems.is_empty()) || uses_type_params(&self.generics, ty)
+    }
+
+    /// The type of the builder representing that the field is set, which is the type of the field.
+    /// For fields having getters or set by presets, it is wrapped by `Set` if the type may be `()`.
+    pub fn set_slot(&self, f: &Field) -> TokenStream {
+        let ty = &f.ty;
+        if (f.attrs.getter || f.attrs.preset) && self.may_be_unit(ty) {
+            quote! { ::builder_pattern::setter::Set<#ty> }
+        } else {
+            quote! { #ty }
+        }
+    }
+
+    /// The type of the builder representing that the field is set by a preset.
+    pub fn preset_slot(&self, f: &Field) -> TokenStream {
+        let ty = &f.ty;
+        quote! { ::builder_pattern::setter::Preset<#ty> }
+    }
+
+    /// Types of the builder representing that the field is set, including by presets.
+    pub fn filled_slots(&self, f: &Field) -> Vec<TokenStream> {
+        let mut slots = vec![self.set_slot(f)];
+        if f.attrs.preset {
+            slots.push(self.preset_slot(f));
+        }
+        slots
+    }
+
     /// Name of the builder structure.
     pub fn builder_name(&self) -> Ident {
-        Ident::new(&format!("{}Builder", self.ident), Span::call_site())
+        match &self.attrs.name {
+            Some(name) => name.clone(),
+            None => Ident::new(&format!("{}Builder", self.ident.unraw()), Span::call_site()),
+        }
+    }
+
+    /// Visibility of the builder and the constructor.
+    pub fn builder_vis(&self) -> &Visibility {
+        self.attrs.vis.as_ref().unwrap_or(&self.vis)
+    }
+
+    /// Visibility of the build function.
+    pub fn build_fn_vis(&self) -> &Visibility {
+        self.attrs
+            .build_fn
+            .vis
+            .as_ref()
+            .unwrap_or_else(|| self.builder_vis())
+    }
+
+    /// Name of the structure having resolved fields, which is used with `build_fn(skip)`.
+    pub fn parts_name(&self) -> Ident {
+        Ident::new(&format!("{}Parts", self.ident.unraw()), Span::call_site())
+    }
+
+    /// Name of the function creating the builder.
+    pub fn constructor_name(&self) -> Ident {
+        match &self.attrs.constructor {
+            Some(name) => name.clone(),
+            None => Ident::new("new", Span::call_site()),
+        }
+    }
+
+    /// Name of the setter of the field, made of the prefix, the name of the field and `suffix`.
+    pub fn setter_name(&self, f: &Field, suffix: &str) -> Ident {
+        let prefix = self.attrs.prefix.as_deref().unwrap_or("");
+        make_ident(&format!("{}{}{}", prefix, f.name(), suffix))
+    }
+
+    pub fn lazy_setter_name(&self, f: &Field) -> Ident {
+        let suffix = self.attrs.lazy_suffix.as_deref().unwrap_or("_lazy");
+        self.setter_name(f, suffix)
+    }
+
+    pub fn async_setter_name(&self, f: &Field) -> Ident {
+        let suffix = self.attrs.async_suffix.as_deref().unwrap_or("_async");
+        self.setter_name(f, suffix)
     }
 
     pub fn fn_lifetime(&self) -> Lifetime {
@@ -129,7 +298,6 @@ impl StructInput {
     /// An iterator for generics like [U1, U2, ...].
     pub fn all_generics(&self) -> impl Iterator<Item = TokenStream> {
         (0..(self.num_fields()))
-            .into_iter()
             .map(|i| TokenStream::from_str(&format!("TyBuilderPattern{}", i + 1)).unwrap())
     }
 
@@ -248,3 +416,16 @@ impl StructInput {
         tokens
     }
 }
+
+/// Whether the type depends on type parameters of the generics.
+fn uses_type_params(generics: &Generics, ty: &Type) -> bool {
+    fn has_ident(tokens: TokenStream, idents: &[&Ident]) -> bool {
+        tokens.into_iter().any(|t| match t {
+            TokenTree::Ident(i) => idents.contains(&&i),
+            TokenTree::Group(g) => has_ident(g.stream(), idents),
+            _ => false,
+        })
+    }
+    let params = generics.type_params().map(|p| &p.ident).collect::<Vec<_>>();
+    has_ident(ty.to_token_stream(), &params)
+}
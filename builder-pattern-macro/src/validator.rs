@@ -0,0 +1,274 @@
+use crate::attributes::parse_keyed_args;
+
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::ToTokens;
+use syn::{
+    parse::{Parse, ParseStream, Result},
+    parse_quote,
+    spanned::Spanned,
+    Expr, ExprCall, LitStr, Token, Type,
+};
+
+/// Built-in validators in `builder_pattern::validators` which are used as functions.
+/// They are written with the `builtin::` prefix, like `builtin::email`.
+const BUILTIN_FUNCTIONS: [&str; 4] = ["non_empty", "url", "email", "path_exists"];
+/// Built-in validators in `builder_pattern::validators` which are made with arguments.
+const BUILTIN_MAKERS: [&str; 2] = ["range", "one_of"];
+
+/// A validator of a field, given via `#[validator(...)]`.
+pub enum Validator {
+    /// A function taking the value and returning `Result<T, E>`.
+    Function(Expr),
+    /// A built-in validator returning `Result<T, ValidationError>`.
+    Builtin(Expr),
+    /// A function taking a reference of the value and returning `Result<(), E>`.
+    Ref(Expr),
+    /// A function taking a reference of the value and returning `bool`.
+    Predicate(Expr, LitStr),
+    /// An expression of `bool` with the value bound to `v`.
+    Check(Expr, LitStr),
+    /// Validators which should be passed in order.
+    All(Vec<Validator>),
+    /// Validators one of which should be passed. It requires `T: Clone`.
+    Any(Vec<Validator>),
+}
+
+impl Parse for Validator {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let keyed = input.peek(Token![ref])
+            || (input.peek(syn::Ident) && input.peek2(Token![=]) && !input.peek2(Token![==]));
+        if !keyed {
+            return Ok(Validator::from_expr(input.parse()?));
+        }
+
+        let mut function: Option<Expr> = None;
+        let mut check: Option<Expr> = None;
+        let mut message: Option<LitStr> = None;
+        parse_keyed_args(input, |key, input| {
+            input.parse::<Token![=]>()?;
+            if key == "ref" {
+                function = Some(input.parse()?);
+            } else if key == "check" {
+                check = Some(input.parse()?);
+            } else if key == "message" {
+                message = Some(input.parse()?);
+            } else {
+                return Err(input.error(format!("unknown option `{}`", key)));
+            }
+            Ok(())
+        })?;
+        Ok(match (function, check, message) {
+            (Some(function), None, Some(message)) => Validator::Predicate(function, message),
+            (Some(function), None, None) => Validator::Ref(function),
+            (None, Some(check), message) => {
+                let message = message.unwrap_or_else(|| {
+                    let doc = format!("check: `{}` is not satisfied.", check.to_token_stream());
+                    LitStr::new(&doc, check.span())
+                });
+                Validator::Check(check, message)
+            }
+            _ => unimplemented!("Invalid validator."),
+        })
+    }
+}
+
+impl Validator {
+    /// Make a validator from an expression, resolving built-in validators and combinators.
+    /// Only paths starting with `builtin::` are resolved, so functions of the user having the
+    /// same names are not shadowed. Unknown names are functions of the user as well.
+    fn from_expr(expr: Expr) -> Validator {
+        if let Expr::Call(call) = &expr {
+            match builtin_ident(&call.func) {
+                Some(i) if i == "all" || i == "any" => {
+                    let validators: Vec<_> =
+                        call.args.iter().cloned().map(Self::from_expr).collect();
+                    if validators.is_empty() {
+                        unimplemented!("`{}` requires at least one validator.", i)
+                    }
+                    return if i == "all" {
+                        Validator::All(validators)
+                    } else {
+                        Validator::Any(validators)
+                    };
+                }
+                Some(i) if i == "len" => return Validator::Builtin(resolve_len(call)),
+                // The pattern is compiled once for the use site, instead of every validation.
+                Some(i) if i == "matches" && call.args.len() == 1 => {
+                    let pattern = &call.args[0];
+                    return Validator::Builtin(parse_quote!(|value| {
+                        static PATTERN: ::builder_pattern::validators::Pattern =
+                            ::builder_pattern::validators::Pattern::new(#pattern);
+                        PATTERN.validate(value)
+                    }));
+                }
+                Some(i) if BUILTIN_MAKERS.contains(&i.to_string().as_str()) => {
+                    let args = &call.args;
+                    return Validator::Builtin(
+                        parse_quote!(::builder_pattern::validators::#i(#args)),
+                    );
+                }
+                _ => {}
+            }
+        } else if let Some(i) = builtin_ident(&expr) {
+            if BUILTIN_FUNCTIONS.contains(&i.to_string().as_str()) {
+                return Validator::Builtin(parse_quote!(::builder_pattern::validators::#i));
+            }
+        }
+        Validator::Function(expr)
+    }
+
+    /// Whether the validator contains built-in validators returning `ValidationError`.
+    fn uses_builtin(&self) -> bool {
+        match self {
+            Validator::Builtin(_) => true,
+            Validator::All(v) | Validator::Any(v) => v.iter().any(Self::uses_builtin),
+            _ => false,
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        match self {
+            Validator::Function(f)
+            | Validator::Builtin(f)
+            | Validator::Ref(f)
+            | Validator::Predicate(f, _)
+            | Validator::Check(f, _) => f.span(),
+            Validator::All(v) | Validator::Any(v) => v[0].span(),
+        }
+    }
+
+    /// An expression validating `value`, which is evaluated as `Result<#ty, E>`.
+    pub fn call(&self, ty: &Type, value: TokenStream) -> TokenStream {
+        match self {
+            Validator::Function(f) | Validator::Builtin(f) => quote_spanned! { f.span() =>
+                (#f)(#value)
+            },
+            Validator::Ref(f) => quote_spanned! { f.span() =>
+                {
+                    let value: #ty = #value;
+                    match (#f)(&value) {
+                        Ok(_) => Ok(value),
+                        Err(e) => Err(e),
+                    }
+                }
+            },
+            Validator::Predicate(f, message) => quote_spanned! { f.span() =>
+                {
+                    let value: #ty = #value;
+                    if (#f)(&value) {
+                        Ok(value)
+                    } else {
+                        Err(#message)
+                    }
+                }
+            },
+            Validator::Check(check, message) => quote_spanned! { check.span() =>
+                {
+                    let v: #ty = #value;
+                    if #check {
+                        Ok(v)
+                    } else {
+                        Err(#message)
+                    }
+                }
+            },
+            Validator::All(validators) => {
+                // Errors should have the same type, so they are converted into
+                // `ValidationError` if there is any built-in validator.
+                let map_err = if self.uses_builtin() {
+                    Some(quote! {
+                        .map_err(::core::convert::Into::<::builder_pattern::validators::ValidationError>::into)
+                    })
+                } else {
+                    None
+                };
+                let calls = validators.iter().map(|v| {
+                    let call = v.call(ty, quote! {value});
+                    quote! { #call #map_err }
+                });
+                quote! {
+                    {
+                        let value: #ty = #value;
+                        Ok(value)#(.and_then(|value: #ty| #calls))*
+                    }
+                }
+            }
+            Validator::Any(validators) => {
+                // The value is cloned for all of validators except the last one.
+                let (last, rest) = validators.split_last().unwrap();
+                let calls = rest
+                    .iter()
+                    .map(|v| v.call(ty, quote! {value.clone()}))
+                    .chain(core::iter::once(last.call(ty, quote! {value})));
+                quote! {
+                    {
+                        let value: #ty = #value;
+                        Err(())#(.or_else(|_| #calls))*
+                    }
+                }
+            }
+        }
+    }
+
+    /// An expression validating `value` like [`Validator::call`], which is evaluated as
+    /// `Result<#ty, &'static str>` to be deferred until the structure is built.
+    pub fn call_deferred(&self, ty: &Type, value: TokenStream) -> TokenStream {
+        let call = self.call(ty, value);
+        if self.uses_builtin() {
+            quote_spanned! { self.span() =>
+                match #call {
+                    Ok(value) => Ok(value),
+                    Err(e) => Err(::core::convert::Into::<&'static str>::into(e)),
+                }
+            }
+        } else {
+            call
+        }
+    }
+}
+
+/// An identifier of the expression if it is a path having only one segment.
+fn single_ident(expr: &Expr) -> Option<&Ident> {
+    match expr {
+        Expr::Path(p) if p.qself.is_none() => p.path.get_ident(),
+        _ => None,
+    }
+}
+
+/// A name of the built-in validator if the expression is a path like `builtin::name`.
+fn builtin_ident(expr: &Expr) -> Option<&Ident> {
+    match expr {
+        Expr::Path(p) if p.qself.is_none() && p.path.leading_colon.is_none() => {
+            match p.path.segments.iter().collect::<Vec<_>>().as_slice() {
+                [prefix, name] if prefix.ident == "builtin" && prefix.arguments.is_empty() => {
+                    Some(&name.ident)
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// `len` takes `min` and `max` as positional or named arguments.
+fn resolve_len(call: &ExprCall) -> Expr {
+    let mut min: Expr = parse_quote!(0);
+    let mut max: Expr = parse_quote!(usize::MAX);
+    let args: Vec<_> = call.args.iter().collect();
+    match args.as_slice() {
+        [Expr::Assign(_), ..] => args.iter().for_each(|arg| match arg {
+            Expr::Assign(a) => match single_ident(&a.left) {
+                Some(i) if i == "min" => min = (*a.right).clone(),
+                Some(i) if i == "max" => max = (*a.right).clone(),
+                _ => unimplemented!("`len` takes `min` and `max`."),
+            },
+            _ => unimplemented!("`len` takes `min` and `max`."),
+        }),
+        [a, b] => {
+            min = (*a).clone();
+            max = (*b).clone();
+        }
+        _ => unimplemented!("`len` takes `min` and `max`."),
+    }
+    parse_quote!(::builder_pattern::validators::len(#min, #max))
+}
@@ -0,0 +1,118 @@
+use builder_pattern::Builder;
+
+#[derive(Builder, Debug, PartialEq)]
+struct Test {
+    #[validator_async(is_available)]
+    #[setter(value, lazy, async)]
+    pub port: u16,
+    #[default(10)]
+    #[validator(is_positive)]
+    #[validator_async(is_even)]
+    #[setter(map)]
+    pub retries: i32,
+    #[default(1)]
+    #[validator_async(|v: u8| async move {
+        if v > 0 {
+            Ok(v)
+        } else {
+            Err("No workers.")
+        }
+    })]
+    #[setter(value, lazy)]
+    pub workers: u8,
+}
+
+async fn is_available(port: u16) -> Result<u16, &'static str> {
+    if port != 8080 {
+        Ok(port)
+    } else {
+        Err("The port is already in use.")
+    }
+}
+
+async fn is_even(v: i32) -> Result<i32, &'static str> {
+    if v % 2 == 0 {
+        Ok(v)
+    } else {
+        Err("Value is odd.")
+    }
+}
+
+fn is_positive(v: i32) -> Result<i32, &'static str> {
+    if v > 0 {
+        Ok(v)
+    } else {
+        Err("Value is negative or zero.")
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    // Value setters are validated asynchronously.
+    // The structure should be built asynchronously.
+    let t1 = Test::new().port(3000).workers(2).build().await;
+    println!("{:?}", t1);
+    assert_eq!(
+        t1,
+        Ok(Test {
+            port: 3000,
+            retries: 10,
+            workers: 2
+        })
+    );
+
+    let t2 = Test::new().port_lazy(|| 8080).build().await;
+    println!("{:?}", t2);
+    assert_eq!(t2, Err("The port is already in use."));
+
+    let t3 = Test::new()
+        .port_async(|| async { 3000 })
+        .retries(3)
+        .unwrap()
+        .build()
+        .await;
+    println!("{:?}", t3);
+    assert_eq!(t3, Err("Value is odd."));
+
+    // Asynchronous validators can be closures.
+    let t4 = Test::new().port(3000).workers_lazy(|| 0).build().await;
+    println!("{:?}", t4);
+    assert_eq!(t4, Err("No workers."));
+
+    // The value is resolved asynchronously to be transformed,
+    // and validated again.
+    let t5 = Test::new()
+        .port(3000)
+        .retries(4)
+        .unwrap()
+        .map_retries(|v| v * 2)
+        .await
+        .unwrap()
+        .build()
+        .await;
+    println!("{:?}", t5);
+    assert_eq!(
+        t5,
+        Ok(Test {
+            port: 3000,
+            retries: 8,
+            workers: 1
+        })
+    );
+    let t6 = Test::new().retries(4).unwrap().map_retries(|v| -v).await;
+    assert!(t6.is_err());
+    let t7 = Test::new()
+        .port(3000)
+        .retries(4)
+        .unwrap()
+        .map_retries(|v| v + 1)
+        .await
+        .unwrap()
+        .build()
+        .await;
+    assert_eq!(t7, Err("Value is odd."));
+
+    // Synchronous validators are evaluated immediately.
+    let t8 = Test::new().port(3000).retries(-2);
+    assert!(t8.is_err());
+}
@@ -0,0 +1,76 @@
+use builder_pattern::Builder;
+
+#[derive(Builder, Debug, PartialEq)]
+#[builder(build_fn(name = "finish"))]
+struct Point {
+    pub x: i32,
+    #[default(0)]
+    pub y: i32,
+}
+
+#[derive(Builder, Debug, PartialEq)]
+#[builder(build_fn(skip))]
+struct Person {
+    #[validator(is_not_empty)]
+    pub name: String,
+    #[setter(value, lazy)]
+    pub age: u8,
+    #[default(None)]
+    pub email: Option<String>,
+}
+
+fn is_not_empty(name: String) -> Result<String, &'static str> {
+    if name.is_empty() {
+        Err("Name cannot be empty.")
+    } else {
+        Ok(name)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum PersonError {
+    TooYoung(u8),
+}
+
+// `build` is written by hand with the resolved fields.
+// The builder has types of required fields sorted by their names, and then optional fields.
+impl<'a, T> PersonBuilder<'a, u8, String, T, (), ()> {
+    fn build(self) -> Result<Person, PersonError> {
+        let PersonParts { name, age, email } = self.into_parts();
+        if age < 14 {
+            return Err(PersonError::TooYoung(age));
+        }
+        println!("Registered {}", name);
+        Ok(Person { name, age, email })
+    }
+}
+
+fn main() {
+    let p = Point::new().x(1).finish();
+    println!("{:?}", p);
+    assert_eq!(p, Point { x: 1, y: 0 });
+
+    let p1 = Person::new()
+        .name(String::from("Joe"))
+        .unwrap()
+        .age(30)
+        .build();
+    println!("{:?}", p1);
+    assert_eq!(
+        p1,
+        Ok(Person {
+            name: String::from("Joe"),
+            age: 30,
+            email: None,
+        })
+    );
+
+    let p2 = Person::new()
+        .name(String::from("Jane"))
+        .unwrap()
+        .age_lazy(|| 10)
+        .email(Some(String::from("jane@example.com")))
+        .build();
+    println!("{:?}", p2);
+    assert_eq!(p2, Err(PersonError::TooYoung(10)));
+}
@@ -0,0 +1,100 @@
+use builder_pattern::{validators::ValidationError, Builder};
+
+#[derive(Builder, Debug, PartialEq)]
+struct Test {
+    #[into]
+    #[validator(builtin::all(builtin::non_empty, builtin::len(max = 8)))]
+    #[setter(value, lazy)]
+    pub name: String,
+    #[validator(builtin::range(0..=150))]
+    pub age: u8,
+    #[default("user")]
+    #[validator(builtin::one_of(["admin", "user"]))]
+    pub role: &'static str,
+    #[default(String::new())]
+    #[validator(builtin::any(builtin::email, builtin::url))]
+    pub contact: String,
+    #[default(".")]
+    #[validator(builtin::path_exists)]
+    pub home: &'static str,
+    // Validators without the prefix are your own functions.
+    #[default("")]
+    #[validator(builtin::all(email, builtin::len(max = 8)))]
+    pub alias: &'static str,
+}
+
+fn email(v: &'static str) -> Result<&'static str, &'static str> {
+    if v.contains(char::is_whitespace) {
+        Err("The alias has whitespaces.")
+    } else {
+        Ok(v)
+    }
+}
+
+fn main() {
+    let t1 = Test::new()
+        .name("Joe")
+        .unwrap()
+        .age(27)
+        .unwrap()
+        .role("admin")
+        .unwrap()
+        .contact(String::from("joe@example.com"))
+        .unwrap()
+        .build();
+    println!("{:?}", t1);
+    assert_eq!(t1.role, "admin");
+
+    let t2 = Test::new().name("");
+    assert!(t2.is_err());
+    if let Err(e) = t2 {
+        println!("{}", e);
+    }
+
+    let t3 = Test::new().name("Lorem ipsum");
+    assert!(t3.is_err());
+    if let Err(e) = t3 {
+        println!("{}", e);
+    }
+
+    let t4 = Test::new().age(200);
+    assert!(t4.is_err());
+
+    let t5 = Test::new().role("guest");
+    assert!(t5.is_err());
+
+    let t6 = Test::new().contact(String::from("https://example.com"));
+    assert!(t6.is_ok());
+    let t7 = Test::new().contact(String::from("Joe"));
+    assert!(t7.is_err());
+    if let Err(e) = t7 {
+        println!("{}", e);
+    }
+
+    let t8 = Test::new().home("/not/existing/path");
+    assert!(t8.is_err());
+
+    let t10 = Test::new().alias("joe");
+    assert!(t10.is_ok());
+    let t11 = Test::new().alias("joe doe");
+    assert!(t11.is_err());
+    if let Err(e) = t11 {
+        println!("{}", e);
+    }
+
+    // The errors name the failed rules.
+    let e = builder_pattern::validators::email("Joe").unwrap_err();
+    println!("{}", e);
+    assert_eq!(
+        e,
+        ValidationError {
+            rule: "email",
+            message: "email: the value is not a valid email address."
+        }
+    );
+
+    // Errors of the lazy setters are returned when it is built.
+    let t9 = Test::new().name_lazy(|| "").age(27).unwrap().build();
+    println!("{:?}", t9);
+    assert_eq!(t9, Err("non_empty: the value is empty."));
+}
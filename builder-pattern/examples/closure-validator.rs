@@ -0,0 +1,48 @@
+use builder_pattern::Builder;
+
+#[derive(Builder, Debug, PartialEq)]
+struct Test {
+    #[validator(|v: i32| if v > 0 { Ok(v) } else { Err("neg") })]
+    #[setter(value, lazy)]
+    pub positive: i32,
+    #[default(0)]
+    #[validator(check = v % 2 == 0, message = "must be even")]
+    pub even: i32,
+    #[into]
+    #[default(String::from("Joe"))]
+    #[validator(check = !v.is_empty())]
+    pub name: String,
+}
+
+fn main() {
+    let t1 = Test::new().positive(3).unwrap().even(4).unwrap().build();
+    println!("{:?}", t1);
+    assert_eq!(
+        t1,
+        Test {
+            positive: 3,
+            even: 4,
+            name: String::from("Joe")
+        }
+    );
+
+    let t2 = Test::new().positive(-3);
+    assert!(t2.is_err());
+
+    let t3 = Test::new().even(3);
+    assert!(t3.is_err());
+    if let Err(e) = t3 {
+        println!("{}", e);
+    }
+
+    // A default message is used if `message` is not given.
+    let t4 = Test::new().name("");
+    assert!(t4.is_err());
+    if let Err(e) = t4 {
+        println!("{}", e);
+    }
+
+    let t5 = Test::new().positive_lazy(|| -3).build();
+    println!("{:?}", t5);
+    assert_eq!(t5, Err("neg"));
+}
@@ -0,0 +1,54 @@
+use builder_pattern::Builder;
+use std::sync::Arc;
+
+struct Request {
+    path: String,
+}
+
+#[derive(Debug, PartialEq)]
+struct Response {
+    status: u16,
+    body: String,
+}
+
+#[derive(Builder)]
+struct Server {
+    #[setter(closure)]
+    pub handler: Box<dyn Fn(Request) -> Response + Send>,
+    #[setter(strip_option, closure)]
+    pub on_error: Option<Arc<dyn Fn(u16) -> String>>,
+    #[default(Box::new(|| {}))]
+    #[setter(closure)]
+    pub on_close: Box<dyn FnMut()>,
+}
+
+fn main() {
+    let mut closed = 0;
+    // Types of arguments of closures are inferred.
+    let mut server = Server::new()
+        .handler(|r| Response {
+            status: 200,
+            body: r.path,
+        })
+        .on_error(|status| format!("Error: {}", status))
+        .on_close(move || {
+            closed += 1;
+            println!("Closed {} time(s).", closed);
+        })
+        .build();
+
+    let res = (server.handler)(Request {
+        path: String::from("/index.html"),
+    });
+    println!("{:?}", res);
+    assert_eq!(
+        res,
+        Response {
+            status: 200,
+            body: String::from("/index.html"),
+        }
+    );
+    let on_error = server.on_error.unwrap();
+    assert_eq!(on_error(404), "Error: 404");
+    (server.on_close)();
+}
@@ -0,0 +1,53 @@
+use builder_pattern::Builder;
+
+#[derive(Builder, Debug, PartialEq)]
+#[builder(
+    constructor(name = "with_url", args(url)),
+    constructor(name = "with_host_port", args(host, port))
+)]
+struct Connection {
+    #[into]
+    #[default(String::from("localhost"))]
+    pub host: String,
+    #[default(80)]
+    #[validator(is_not_zero)]
+    pub port: u16,
+    #[default(None)]
+    pub url: Option<String>,
+    #[default(30)]
+    pub timeout: u32,
+}
+
+fn is_not_zero(port: u16) -> Result<u16, &'static str> {
+    if port != 0 {
+        Ok(port)
+    } else {
+        Err("Port cannot be zero.")
+    }
+}
+
+fn main() {
+    let c1 = Connection::with_url(Some(String::from("https://example.com")))
+        .timeout(10)
+        .build();
+    println!("{:?}", c1);
+    assert_eq!(
+        c1,
+        Connection {
+            host: String::from("localhost"),
+            port: 80,
+            url: Some(String::from("https://example.com")),
+            timeout: 10,
+        }
+    );
+
+    // Arguments follow the rules of value setters, so it is validated.
+    let c2 = Connection::with_host_port("example.com", 8080)
+        .unwrap()
+        .build();
+    println!("{:?}", c2);
+    assert_eq!(c2.host, "example.com");
+    assert_eq!(c2.port, 8080);
+
+    assert!(Connection::with_host_port("example.com", 0).is_err());
+}
@@ -0,0 +1,71 @@
+use builder_pattern::Builder;
+
+struct Context {
+    allowed_users: Vec<&'static str>,
+    root: &'static str,
+}
+
+#[derive(Builder, Debug, PartialEq)]
+#[builder(context = Context)]
+struct Test {
+    #[validator(is_allowed, with_context)]
+    #[setter(value, lazy, async)]
+    pub user: &'static str,
+    #[into]
+    #[default_lazy(|ctx: &Context| format!("{}/home", ctx.root))]
+    pub home: String,
+    #[default_lazy(default_shell, with_context)]
+    pub shell: String,
+}
+
+fn is_allowed(user: &'static str, ctx: &Context) -> Result<&'static str, &'static str> {
+    if ctx.allowed_users.contains(&user) {
+        Ok(user)
+    } else {
+        Err("The user is not allowed.")
+    }
+}
+
+fn default_shell(ctx: &Context) -> String {
+    format!("{}/bin/sh", ctx.root)
+}
+
+#[tokio::main]
+async fn main() {
+    let ctx = Context {
+        allowed_users: vec!["joe", "jack"],
+        root: "/root",
+    };
+
+    let t1 = Test::new().user("joe").build_with(&ctx);
+    println!("{:?}", t1);
+    assert_eq!(
+        t1,
+        Ok(Test {
+            user: "joe",
+            home: String::from("/root/home"),
+            shell: String::from("/root/bin/sh"),
+        })
+    );
+
+    let t2 = Test::new()
+        .user_lazy(|| "sam")
+        .home("/home/sam")
+        .build_with(&ctx);
+    println!("{:?}", t2);
+    assert_eq!(t2, Err("The user is not allowed."));
+
+    let t3 = Test::new()
+        .user_async(|| async { "jack" })
+        .build_with(&ctx)
+        .await;
+    println!("{:?}", t3);
+    assert_eq!(
+        t3,
+        Ok(Test {
+            user: "jack",
+            home: String::from("/root/home"),
+            shell: String::from("/root/bin/sh"),
+        })
+    );
+}
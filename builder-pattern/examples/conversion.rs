@@ -0,0 +1,74 @@
+use builder_pattern::Builder;
+use std::net::IpAddr;
+
+#[derive(Builder, Debug, PartialEq)]
+struct Server {
+    #[setter(value, lazy, parse)]
+    pub ip: IpAddr,
+    #[setter(value, async, try_into)]
+    pub port: u16,
+    #[default(4)]
+    #[validator(is_positive)]
+    #[setter(parse)]
+    pub workers: i32,
+}
+
+fn is_positive(v: i32) -> Result<i32, &'static str> {
+    if v > 0 {
+        Ok(v)
+    } else {
+        Err("Value is negative or zero.")
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let s1 = Server::new()
+        .ip("127.0.0.1")
+        .unwrap()
+        .port(8080_i32)
+        .unwrap()
+        .build();
+    println!("{:?}", s1);
+    assert_eq!(
+        s1,
+        Server {
+            ip: "127.0.0.1".parse().unwrap(),
+            port: 8080,
+            workers: 4,
+        }
+    );
+
+    // Conversion errors are returned immediately from value setters.
+    assert!(Server::new().ip("localhost").is_err());
+    assert!(Server::new().port(-1).is_err());
+    assert!(Server::new().workers("four").is_err());
+    // Validators are evaluated after the conversion.
+    assert!(Server::new().workers("-4").is_err());
+
+    // Conversion errors of lazy and async setters are returned from `build`.
+    let s2 = Server::new()
+        .ip_lazy(|| "::1")
+        .port_async(|| async { 70000 })
+        .build()
+        .await;
+    println!("{:?}", s2);
+    assert_eq!(s2, Err("Conversion of `port` into `u16` failed."));
+
+    let s3 = Server::new()
+        .ip_lazy(|| String::from("::1"))
+        .port(443)
+        .unwrap()
+        .workers("8")
+        .unwrap()
+        .build();
+    println!("{:?}", s3);
+    assert_eq!(
+        s3,
+        Ok(Server {
+            ip: "::1".parse().unwrap(),
+            port: 443,
+            workers: 8,
+        })
+    );
+}
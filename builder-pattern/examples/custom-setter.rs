@@ -0,0 +1,54 @@
+use builder_pattern::Builder;
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[derive(Builder, Debug, PartialEq)]
+struct Config {
+    #[setter(name = "root_str", with = |path: &str| PathBuf::from(path))]
+    pub root: PathBuf,
+    #[default(Duration::from_secs(30))]
+    #[setter(name = "timeout_secs", with = |secs: u64| Duration::from_secs(secs))]
+    #[setter(name = "timeout_mins", with = |mins: u64| Duration::from_secs(mins * 60))]
+    #[validator(is_not_zero)]
+    pub timeout: Duration,
+    #[default(None)]
+    #[setter(name = "credentials", with = |user: &str, password: &str| Some(format!("{}:{}", user, password)))]
+    pub auth: Option<String>,
+}
+
+fn is_not_zero(v: Duration) -> Result<Duration, &'static str> {
+    if v.is_zero() {
+        Err("Timeout is zero.")
+    } else {
+        Ok(v)
+    }
+}
+
+fn main() {
+    let c1 = Config::new()
+        .root_str("/tmp")
+        .timeout_secs(10)
+        .unwrap()
+        .credentials("joe", "1234")
+        .build();
+    println!("{:?}", c1);
+    assert_eq!(
+        c1,
+        Config {
+            root: PathBuf::from("/tmp"),
+            timeout: Duration::from_secs(10),
+            auth: Some(String::from("joe:1234")),
+        }
+    );
+
+    let c2 = Config::new()
+        .root(PathBuf::from("/var"))
+        .timeout_mins(2)
+        .unwrap()
+        .build();
+    println!("{:?}", c2);
+    assert_eq!(c2.timeout, Duration::from_secs(120));
+
+    // Validators are evaluated for custom setters as well.
+    assert!(Config::new().timeout_secs(0).is_err());
+}
@@ -0,0 +1,54 @@
+mod config {
+    use builder_pattern::Builder;
+
+    #[derive(Builder, Debug, PartialEq)]
+    #[builder(vis = "pub(crate)", build_fn(vis = "pub(super)"))]
+    pub struct Config {
+        pub name: String,
+        // The setter is visible only in the parent module.
+        #[default(false)]
+        #[setter(vis = "pub(super)")]
+        debug: bool,
+        #[default(8080)]
+        #[setter(vis = "pub(in crate::config)")]
+        port: u16,
+        // The visibility can be given by a separate attribute.
+        #[default(1)]
+        #[setter(value, lazy)]
+        #[setter(vis = "pub(super)")]
+        workers: u8,
+    }
+
+    impl Config {
+        pub fn port(&self) -> u16 {
+            self.port
+        }
+
+        pub fn debug(&self) -> bool {
+            self.debug
+        }
+
+        pub fn workers(&self) -> u8 {
+            self.workers
+        }
+
+        pub fn with_port(port: u16) -> ConfigBuilder<'static, (), (), u16, (), (), ()> {
+            Config::new().port(port)
+        }
+    }
+}
+
+use config::Config;
+
+fn main() {
+    let c1 = Config::with_port(3000)
+        .name(String::from("app"))
+        .debug(true)
+        .workers_lazy(|| 4)
+        .build();
+    println!("{:?}", c1);
+    assert_eq!(c1.name, "app");
+    assert_eq!(c1.port(), 3000);
+    assert!(c1.debug());
+    assert_eq!(c1.workers(), 4);
+}
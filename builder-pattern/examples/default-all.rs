@@ -0,0 +1,60 @@
+use builder_pattern::Builder;
+use std::collections::HashMap;
+
+#[derive(Builder, Debug, PartialEq)]
+struct Request {
+    pub url: String,
+    // `Default::default()` is used if no value is given.
+    #[default]
+    pub headers: HashMap<String, String>,
+    #[default]
+    pub retries: u32,
+}
+
+#[derive(Builder, Debug, PartialEq)]
+#[builder(default_all)]
+struct Options<T> {
+    #[required]
+    pub name: String,
+    pub verbose: bool,
+    // `T: Default` is required to build the structure.
+    pub extra: T,
+    pub tags: Vec<String>,
+}
+
+fn main() {
+    let r = Request::new().url(String::from("localhost")).build();
+    println!("{:?}", r);
+    assert_eq!(
+        r,
+        Request {
+            url: String::from("localhost"),
+            headers: HashMap::new(),
+            retries: 0,
+        }
+    );
+
+    let o1 = Options::<Option<u8>>::new()
+        .name(String::from("app"))
+        .verbose(true)
+        .build();
+    println!("{:?}", o1);
+    assert_eq!(
+        o1,
+        Options {
+            name: String::from("app"),
+            verbose: true,
+            extra: None,
+            tags: vec![],
+        }
+    );
+
+    let o2 = Options::new()
+        .extra(3)
+        .tags(vec![String::from("a")])
+        .name(String::from("app"))
+        .build();
+    println!("{:?}", o2);
+    assert_eq!(o2.extra, 3);
+    assert!(!o2.verbose);
+}
@@ -0,0 +1,56 @@
+use builder_pattern::Builder;
+use std::collections::{BTreeSet, HashMap};
+
+#[derive(Builder, Debug, PartialEq)]
+struct Command {
+    pub program: String,
+    #[setter(value, each = "arg")]
+    pub args: Vec<String>,
+    #[setter(each = "env")]
+    pub envs: HashMap<String, String>,
+    #[default(vec![80].into_iter().collect())]
+    #[setter(lazy, each = "port")]
+    pub ports: BTreeSet<u16>,
+}
+
+fn main() {
+    // Collections are empty by default.
+    let c1 = Command::new().program(String::from("ls")).build();
+    println!("{:?}", c1);
+    assert_eq!(
+        c1,
+        Command {
+            program: String::from("ls"),
+            args: vec![],
+            envs: HashMap::new(),
+            ports: vec![80].into_iter().collect(),
+        }
+    );
+
+    let c2 = Command::new()
+        .arg(String::from("-l"))
+        .program(String::from("ls"))
+        .arg(String::from("-a"))
+        .env(String::from("LANG"), String::from("C"))
+        .port(443)
+        .build();
+    println!("{:?}", c2);
+    assert_eq!(c2.args, vec![String::from("-l"), String::from("-a")]);
+    assert_eq!(c2.envs.get("LANG").map(String::as_str), Some("C"));
+    assert_eq!(c2.ports, vec![80, 443].into_iter().collect());
+
+    // Items are added after the lazy value is evaluated.
+    let c3 = Command::new()
+        .program(String::from("ls"))
+        .args(vec![String::from("-l")])
+        .extend_args(vec![String::from("-a"), String::from("-h")])
+        .ports_lazy(|| vec![8080].into_iter().collect())
+        .port(8443)
+        .build();
+    println!("{:?}", c3);
+    assert_eq!(
+        c3.args,
+        vec![String::from("-l"), String::from("-a"), String::from("-h")]
+    );
+    assert_eq!(c3.ports, vec![8080, 8443].into_iter().collect());
+}
@@ -0,0 +1,45 @@
+use builder_pattern::Builder;
+
+#[derive(Builder, Debug, PartialEq)]
+struct Options {
+    pub path: &'static str,
+    #[setter(flag)]
+    pub verbose: bool,
+    #[default(true)]
+    #[setter(flag, lazy)]
+    pub color: bool,
+}
+
+fn main() {
+    let o1 = Options::new().path("/tmp").build();
+    println!("{:?}", o1);
+    assert_eq!(
+        o1,
+        Options {
+            path: "/tmp",
+            verbose: false,
+            color: true,
+        }
+    );
+
+    let o2 = Options::new().verbose().path("/tmp").build();
+    println!("{:?}", o2);
+    assert!(o2.verbose);
+
+    // Set the flag conditionally.
+    let quiet = true;
+    let o3 = Options::new()
+        .path("/tmp")
+        .verbose_if(!quiet)
+        .color_lazy(|| false)
+        .build();
+    println!("{:?}", o3);
+    assert_eq!(
+        o3,
+        Options {
+            path: "/tmp",
+            verbose: false,
+            color: false,
+        }
+    );
+}
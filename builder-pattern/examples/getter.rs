@@ -0,0 +1,77 @@
+use builder_pattern::{Builder, FieldState};
+
+#[derive(Builder, Debug, PartialEq)]
+#[builder(getters)]
+struct User {
+    #[setter(value, lazy)]
+    pub name: String,
+    pub email: String,
+    #[default(0)]
+    pub age: u8,
+}
+
+#[derive(Builder, Debug, PartialEq)]
+struct Pair<T> {
+    #[setter(getter)]
+    pub first: T,
+    pub second: T,
+    // Getters are not provided unless they are given, so it doesn't collide with `second_ref`.
+    #[default(None)]
+    pub second_ref: Option<usize>,
+}
+
+// `name` is the second field of the builder, since required fields are sorted by names.
+fn name_state<E, N, A, M, V>(builder: &UserBuilder<'_, E, N, A, M, V>) -> &'static str
+where
+    N: FieldState<User, 1>,
+{
+    if builder.is_name_set() {
+        "set"
+    } else {
+        "unset"
+    }
+}
+
+fn main() {
+    let builder = User::new().name(String::from("Joe"));
+    assert!(builder.is_name_set());
+    assert!(!builder.is_email_set());
+    assert!(!builder.is_age_set());
+    assert_eq!(name_state(&User::new()), "unset");
+    assert_eq!(name_state(&builder), "set");
+
+    // Derive another field from the value which is already set.
+    let name = builder.name_ref().unwrap().to_lowercase();
+    println!("name: {}", name);
+    let user = builder.email(format!("{}@example.com", name)).build();
+    println!("{:?}", user);
+    assert_eq!(
+        user,
+        User {
+            name: String::from("Joe"),
+            email: String::from("joe@example.com"),
+            age: 0,
+        }
+    );
+
+    // Lazy values are not evaluated yet.
+    let builder = User::new().name_lazy(|| String::from("Lazy"));
+    assert!(builder.is_name_set());
+    assert_eq!(builder.name_ref(), None);
+
+    // Fields of type parameters can be checked in any states, even if they are `()`.
+    assert!(!Pair::<i32>::new().is_first_set());
+    assert!(Pair::<()>::new().first(()).is_first_set());
+    let pair = Pair::new().first(1).second(2);
+    assert_eq!(pair.first_ref(), Some(&1));
+    let pair = pair.second_ref(None).build();
+    println!("{:?}", pair);
+    assert_eq!(
+        pair,
+        Pair {
+            first: 1,
+            second: 2,
+            second_ref: None,
+        }
+    );
+}
@@ -0,0 +1,89 @@
+use builder_pattern::Builder;
+use std::time::Duration;
+
+#[derive(Builder, Debug, PartialEq)]
+#[builder(maps)]
+struct Server {
+    #[setter(value, lazy)]
+    pub name: String,
+    #[default(vec![])]
+    pub tags: Vec<String>,
+    #[default(Duration::from_secs(1))]
+    #[validator(at_most_a_minute)]
+    pub timeout: Duration,
+    #[validator(is_not_privileged)]
+    #[setter(value, lazy)]
+    pub port: u16,
+}
+
+fn at_most_a_minute(timeout: Duration) -> Result<Duration, String> {
+    if timeout <= Duration::from_secs(60) {
+        Ok(timeout)
+    } else {
+        Err(format!("{:?} is too long.", timeout))
+    }
+}
+
+fn is_not_privileged(port: u16) -> Result<u16, &'static str> {
+    if port >= 1024 {
+        Ok(port)
+    } else {
+        Err("The port is privileged.")
+    }
+}
+
+fn main() {
+    let s1 = Server::new()
+        .name(String::from("api"))
+        .map_name(|name| name + "-v2")
+        .tags(vec![String::from("web")])
+        .map_tags(|mut tags| {
+            tags.push(String::from("public"));
+            tags
+        })
+        .timeout(Duration::from_secs(10))
+        .unwrap()
+        .map_timeout(|timeout| timeout * 2)
+        .unwrap()
+        .port(8080)
+        .unwrap()
+        .build();
+    println!("{:?}", s1);
+    assert_eq!(
+        s1,
+        Server {
+            name: String::from("api-v2"),
+            tags: vec![String::from("web"), String::from("public")],
+            timeout: Duration::from_secs(20),
+            port: 8080,
+        }
+    );
+
+    // Values validated immediately are validated again after the transformation.
+    let s2 = Server::new()
+        .timeout(Duration::from_secs(40))
+        .unwrap()
+        .map_timeout(|timeout| timeout * 2);
+    assert!(s2.is_err());
+
+    // Lazy values are transformed after they are evaluated.
+    // The validator is applied to the transformed value when the structure is built.
+    let s3 = Server::new()
+        .name_lazy(|| String::from("api"))
+        .map_name(|name| name.to_uppercase())
+        .port_lazy(|| 8080)
+        .map_port(|port| port + 1)
+        .build();
+    println!("{:?}", s3);
+    assert_eq!(s3.as_ref().map(|s| s.name.as_str()), Ok("API"));
+    assert_eq!(s3.map(|s| s.port), Ok(8081));
+
+    let s4 = Server::new()
+        .name(String::from("api"))
+        .port(8080)
+        .unwrap()
+        .map_port(|port| port / 10)
+        .build();
+    println!("{:?}", s4);
+    assert_eq!(s4, Err("The port is privileged."));
+}
@@ -0,0 +1,80 @@
+use builder_pattern::Builder;
+
+#[derive(Builder, Debug, PartialEq)]
+struct Config {
+    pub name: String,
+    #[default(8080)]
+    #[validator(is_not_zero)]
+    #[setter(value, lazy, async)]
+    pub port: u16,
+    #[default(String::from("localhost"))]
+    #[into]
+    pub host: String,
+}
+
+#[derive(Builder, Debug, PartialEq)]
+struct Flags {
+    #[default(false)]
+    pub verbose: bool,
+    // `maybe_verbose` of `verbose` is not provided, because it is the setter of this field.
+    #[default(true)]
+    pub maybe_verbose: bool,
+}
+
+fn is_not_zero(v: u16) -> Result<u16, &'static str> {
+    if v != 0 {
+        Ok(v)
+    } else {
+        Err("Port is zero.")
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    // Values given by users, e.g. from command line arguments.
+    let port: Option<u16> = Some(3000);
+    let host: Option<&str> = None;
+
+    let c1 = Config::new()
+        .name(String::from("server"))
+        .maybe_port(port)
+        .unwrap()
+        .maybe_host(host)
+        .build();
+    println!("{:?}", c1);
+    assert_eq!(
+        c1,
+        Config {
+            name: String::from("server"),
+            port: 3000,
+            host: String::from("localhost"),
+        }
+    );
+
+    // The default value is kept for `None`.
+    let c2 = Config::new()
+        .name(String::from("server"))
+        .maybe_port_lazy(None::<fn() -> u16>)
+        .build()
+        .unwrap();
+    println!("{:?}", c2);
+    assert_eq!(c2.port, 8080);
+
+    let c3 = Config::new()
+        .name(String::from("server"))
+        .maybe_port_async(Some(|| async { 0 }))
+        .build()
+        .await;
+    println!("{:?}", c3);
+    assert_eq!(c3, Err("Port is zero."));
+
+    let f = Flags::new().maybe_verbose(false).build();
+    println!("{:?}", f);
+    assert_eq!(
+        f,
+        Flags {
+            verbose: false,
+            maybe_verbose: false,
+        }
+    );
+}
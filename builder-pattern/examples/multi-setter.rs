@@ -0,0 +1,57 @@
+use builder_pattern::Builder;
+
+#[derive(Builder, Debug, PartialEq)]
+#[builder(setter(name = "size", fields(width, height)))]
+#[builder(setter(name = "address", fields(host, port), replace))]
+struct Window {
+    pub width: u32,
+    pub height: u32,
+    #[into]
+    pub host: String,
+    #[validator(is_not_zero)]
+    pub port: u16,
+    #[default(String::from("Untitled"))]
+    pub title: String,
+}
+
+fn is_not_zero(v: u16) -> Result<u16, &'static str> {
+    if v != 0 {
+        Ok(v)
+    } else {
+        Err("Port is zero.")
+    }
+}
+
+fn main() {
+    // `width` and `height` are set at once.
+    let w1 = Window::new()
+        .size(640, 480)
+        .address("localhost", 8080)
+        .unwrap()
+        .build();
+    println!("{:?}", w1);
+    assert_eq!(
+        w1,
+        Window {
+            width: 640,
+            height: 480,
+            host: String::from("localhost"),
+            port: 8080,
+            title: String::from("Untitled"),
+        }
+    );
+
+    // Setters of each field are still provided unless `replace` is given.
+    let w2 = Window::new()
+        .width(800)
+        .height(600)
+        .address("localhost", 80)
+        .unwrap()
+        .title(String::from("Main"))
+        .build();
+    println!("{:?}", w2);
+    assert_eq!(w2.width, 800);
+
+    // Validators are evaluated as well.
+    assert!(Window::new().address("localhost", 0).is_err());
+}
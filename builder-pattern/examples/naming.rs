@@ -0,0 +1,77 @@
+use builder_pattern::Builder;
+
+#[derive(Builder, Debug, PartialEq)]
+#[builder(
+    name = "RequestConfig",
+    constructor = "builder",
+    prefix = "with_",
+    lazy_suffix = "_with",
+    async_suffix = "_later"
+)]
+struct Request {
+    #[setter(value, lazy)]
+    pub r#type: String,
+    #[setter(value, async, rename = "address", getter)]
+    pub url: String,
+    #[default(3)]
+    pub retries: u8,
+}
+
+impl Request {
+    // The hand-written `new` doesn't collide with the constructor of the builder.
+    pub fn new(url: &str) -> Self {
+        Request {
+            r#type: String::from("GET"),
+            url: String::from(url),
+            retries: 3,
+        }
+    }
+}
+
+#[derive(Builder, Debug, PartialEq)]
+#[builder(getters)]
+struct Item {
+    pub r#type: String,
+    #[default(false)]
+    #[setter(value, lazy)]
+    pub r#ref: bool,
+}
+
+#[tokio::main]
+async fn main() {
+    let config: RequestConfig<_, _, _, _, _> = Request::builder()
+        .with_type_with(|| String::from("POST"))
+        .with_address_later(|| async { String::from("localhost") })
+        .with_retries(5);
+    assert!(config.is_address_set());
+    let r1 = config.build().await;
+    println!("{:?}", r1);
+    assert_eq!(
+        r1,
+        Request {
+            r#type: String::from("POST"),
+            url: String::from("localhost"),
+            retries: 5,
+        }
+    );
+    assert_eq!(Request::new("localhost").url, "localhost");
+
+    // Names of raw identifiers are made without `r#`.
+    let i1 = Item::new()
+        .r#type(String::from("book"))
+        .ref_lazy(|| true)
+        .build();
+    println!("{:?}", i1);
+    assert_eq!(
+        i1,
+        Item {
+            r#type: String::from("book"),
+            r#ref: true,
+        }
+    );
+    assert!(Item::new()
+        .maybe_ref(None)
+        .r#type(String::new())
+        .type_ref()
+        .is_some());
+}
@@ -0,0 +1,57 @@
+use builder_pattern::Builder;
+
+#[derive(Builder, Debug, PartialEq)]
+struct Config {
+    #[setter(value, lazy, overridable)]
+    pub name: String,
+    #[default(8080)]
+    #[setter(overridable)]
+    pub port: u16,
+    #[default(false)]
+    pub debug: bool,
+}
+
+#[derive(Builder, Debug, PartialEq)]
+#[builder(overridable)]
+struct Point {
+    #[default(0)]
+    pub x: i32,
+    #[default(0)]
+    pub y: i32,
+}
+
+fn main() {
+    // A base builder shared by tests.
+    let base = || Config::new().name(String::from("base")).port(3000);
+
+    // Fields can be set again.
+    let c1 = base().name(String::from("test")).debug(true).build();
+    println!("{:?}", c1);
+    assert_eq!(
+        c1,
+        Config {
+            name: String::from("test"),
+            port: 3000,
+            debug: true,
+        }
+    );
+
+    // `reset_` restores the default value.
+    let c2 = base()
+        .name_lazy(|| String::from("lazy"))
+        .reset_port()
+        .build();
+    println!("{:?}", c2);
+    assert_eq!(
+        c2,
+        Config {
+            name: String::from("lazy"),
+            port: 8080,
+            debug: false,
+        }
+    );
+
+    let p = Point::new().x(1).y(2).x(3).reset_y().build();
+    println!("{:?}", p);
+    assert_eq!(p, Point { x: 3, y: 0 });
+}
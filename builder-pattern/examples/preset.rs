@@ -0,0 +1,72 @@
+use builder_pattern::Builder;
+
+#[derive(Builder, Debug, PartialEq)]
+#[builder(preset(
+    dev(host = "localhost", port = 8080, debug = true),
+    prod(port = 443, workers = 16)
+))]
+struct Config {
+    #[into]
+    pub host: String,
+    #[validator(is_not_zero)]
+    #[setter(map)]
+    pub port: u16,
+    #[default(false)]
+    pub debug: bool,
+    #[default(4)]
+    pub workers: u32,
+}
+
+fn is_not_zero(port: u16) -> Result<u16, &'static str> {
+    if port != 0 {
+        Ok(port)
+    } else {
+        Err("Port cannot be zero.")
+    }
+}
+
+// Helpers start from presets instead of returning builders.
+fn dev_config() -> Result<Config, String> {
+    Ok(Config::dev()?.workers(1).build())
+}
+
+fn main() {
+    let c1 = dev_config().unwrap();
+    println!("{:?}", c1);
+    assert_eq!(
+        c1,
+        Config {
+            host: String::from("localhost"),
+            port: 8080,
+            debug: true,
+            workers: 1,
+        }
+    );
+
+    // Fields set by the preset can be overridden, and they are validated again.
+    let c2 = Config::prod()
+        .unwrap()
+        .host("example.com")
+        .workers(32)
+        .build();
+    println!("{:?}", c2);
+    assert_eq!(c2.port, 443);
+    assert_eq!(c2.workers, 32);
+    assert!(!c2.debug);
+
+    assert!(Config::dev().unwrap().port(0).is_err());
+
+    // Values set by presets can be transformed as well.
+    let c3 = Config::dev()
+        .unwrap()
+        .map_port(|port| port + 1)
+        .unwrap()
+        .build();
+    println!("{:?}", c3);
+    assert_eq!(c3.port, 8081);
+
+    // Builders from `new` are not affected by presets.
+    let c4 = Config::new().host("example.com").port(80).unwrap().build();
+    println!("{:?}", c4);
+    assert_eq!(c4.workers, 4);
+}
@@ -0,0 +1,51 @@
+use builder_pattern::Builder;
+
+#[derive(Builder, Debug, PartialEq)]
+struct Test {
+    #[into]
+    #[validator(ref = not_empty)]
+    #[setter(value, lazy)]
+    pub name: String,
+    #[validator(ref = is_positive, message = "Value is negative or zero.")]
+    pub age: i32,
+}
+
+fn not_empty(name: &str) -> Result<(), &'static str> {
+    if name.is_empty() {
+        Err("Name cannot be empty.")
+    } else {
+        Ok(())
+    }
+}
+
+fn is_positive(v: &i32) -> bool {
+    *v > 0
+}
+
+fn main() {
+    let t1 = Test::new().name("Joe").unwrap().age(27).unwrap().build();
+    println!("{:?}", t1);
+    assert_eq!(
+        t1,
+        Test {
+            name: String::from("Joe"),
+            age: 27
+        }
+    );
+
+    let t2 = Test::new().name("");
+    assert!(t2.is_err());
+
+    let t3 = Test::new().age(0);
+    assert!(t3.is_err());
+    if let Err(e) = t3 {
+        println!("{}", e);
+    }
+
+    // Validators taking a reference can be used for other code.
+    assert!(is_positive(&t1.age));
+
+    let t4 = Test::new().name_lazy(|| "").age(27).unwrap().build();
+    println!("{:?}", t4);
+    assert_eq!(t4, Err("Name cannot be empty."));
+}
@@ -0,0 +1,71 @@
+use builder_pattern::Builder;
+use std::time::Duration;
+
+#[derive(Builder, Debug, PartialEq)]
+struct Request {
+    pub url: String,
+    #[setter(value, lazy, strip_option)]
+    pub timeout: Option<Duration>,
+    #[into]
+    #[setter(strip_option)]
+    pub user_agent: Option<String>,
+    #[default(Some(3))]
+    #[setter(strip_option)]
+    pub retries: Option<u32>,
+}
+
+#[derive(Builder, Debug, PartialEq)]
+#[builder(strip_option)]
+struct Person {
+    pub name: String,
+    #[into]
+    pub email: Option<String>,
+    pub age: Option<u8>,
+}
+
+fn main() {
+    // Fields of `Option<T>` are `None` by default.
+    let r1 = Request::new().url(String::from("localhost")).build();
+    println!("{:?}", r1);
+    assert_eq!(
+        r1,
+        Request {
+            url: String::from("localhost"),
+            timeout: None,
+            user_agent: None,
+            retries: Some(3),
+        }
+    );
+
+    let r2 = Request::new()
+        .url(String::from("localhost"))
+        .timeout_lazy(|| Duration::from_secs(10))
+        .user_agent("curl")
+        .retries(5)
+        .build();
+    println!("{:?}", r2);
+    assert_eq!(
+        r2,
+        Request {
+            url: String::from("localhost"),
+            timeout: Some(Duration::from_secs(10)),
+            user_agent: Some(String::from("curl")),
+            retries: Some(5),
+        }
+    );
+
+    // `strip_option` of the structure is applied to all fields of `Option<T>`.
+    let p = Person::new()
+        .name(String::from("Joe"))
+        .email("joe@example.com")
+        .build();
+    println!("{:?}", p);
+    assert_eq!(
+        p,
+        Person {
+            name: String::from("Joe"),
+            email: Some(String::from("joe@example.com")),
+            age: None,
+        }
+    );
+}
@@ -0,0 +1,57 @@
+use builder_pattern::Builder;
+
+#[derive(Builder, Debug, PartialEq)]
+#[builder(validate = Range::check, finalize = Range::normalize)]
+struct Range {
+    min: i32,
+    max: i32,
+    #[default(1)]
+    #[validator(is_positive)]
+    #[setter(value, lazy)]
+    step: i32,
+}
+
+impl Range {
+    fn check(self) -> Result<Self, &'static str> {
+        if self.min <= self.max {
+            Ok(self)
+        } else {
+            Err("`min` is greater than `max`.")
+        }
+    }
+
+    fn normalize(mut self) -> Self {
+        self.max -= (self.max - self.min) % self.step;
+        self
+    }
+}
+
+fn is_positive(v: i32) -> Result<i32, &'static str> {
+    if v > 0 {
+        Ok(v)
+    } else {
+        Err("Value is negative or zero.")
+    }
+}
+
+fn main() {
+    let r1 = Range::new().min(0).max(10).step(3).unwrap().build();
+    println!("{:?}", r1);
+    assert_eq!(
+        r1,
+        Ok(Range {
+            min: 0,
+            max: 9,
+            step: 3
+        })
+    );
+
+    let r2 = Range::new().min(5).max(1).build();
+    println!("{:?}", r2);
+    assert_eq!(r2, Err("`min` is greater than `max`."));
+
+    // Lazy validators are evaluated before the structure is validated.
+    let r3 = Range::new().min(5).max(1).step_lazy(|| 0).build();
+    println!("{:?}", r3);
+    assert_eq!(r3, Err("Value is negative or zero."));
+}
@@ -0,0 +1,56 @@
+use builder_pattern::Builder;
+
+#[derive(Builder, Debug, PartialEq)]
+#[builder(validate(ref = Range::check), error = String)]
+struct Range {
+    min: i32,
+    max: i32,
+    #[default(1)]
+    #[validator(is_positive)]
+    #[setter(value, lazy)]
+    step: i32,
+}
+
+impl Range {
+    // Only a reference of the structure is needed to check it.
+    fn check(&self) -> Result<(), String> {
+        if self.min <= self.max {
+            Ok(())
+        } else {
+            Err(format!(
+                "`min`({}) is greater than `max`({}).",
+                self.min, self.max
+            ))
+        }
+    }
+}
+
+fn is_positive(v: i32) -> Result<i32, &'static str> {
+    if v > 0 {
+        Ok(v)
+    } else {
+        Err("Value is negative or zero.")
+    }
+}
+
+fn main() {
+    let r1 = Range::new().min(0).max(10).build();
+    println!("{:?}", r1);
+    assert_eq!(
+        r1,
+        Ok(Range {
+            min: 0,
+            max: 10,
+            step: 1
+        })
+    );
+
+    let r2 = Range::new().min(5).max(1).build();
+    println!("{:?}", r2);
+    assert_eq!(r2, Err(String::from("`min`(5) is greater than `max`(1).")));
+
+    // Errors of lazy validators are converted into the error type.
+    let r3 = Range::new().min(0).max(1).step_lazy(|| 0).build();
+    println!("{:?}", r3);
+    assert_eq!(r3, Err(String::from("Value is negative or zero.")));
+}
@@ -0,0 +1,66 @@
+use builder_pattern::Builder;
+
+#[derive(Builder, Debug, PartialEq)]
+struct Test {
+    #[into]
+    #[transform(|s: String| s.trim().to_string())]
+    #[transform(|s: String| s.to_lowercase())]
+    #[validator(builtin::non_empty)]
+    #[setter(value, lazy, async)]
+    pub name: String,
+    #[default(200)]
+    #[transform(|v: i32| v.min(100), default)]
+    #[setter(value, lazy)]
+    pub percent: i32,
+    #[default_lazy(|| "  Seoul ")]
+    #[transform(str::trim, default)]
+    pub city: &'static str,
+}
+
+#[tokio::main]
+async fn main() {
+    let t1 = Test::new().name("  Joe ").unwrap().build();
+    println!("{:?}", t1);
+    assert_eq!(
+        t1,
+        Test {
+            name: String::from("joe"),
+            percent: 100,
+            city: "Seoul",
+        }
+    );
+
+    // Transforms are applied before the validator.
+    let t2 = Test::new().name("   ");
+    assert!(t2.is_err());
+
+    let t3 = Test::new()
+        .name_lazy(|| "JACK")
+        .percent_lazy(|| 150)
+        .city(" Busan ")
+        .build();
+    println!("{:?}", t3);
+    assert_eq!(
+        t3,
+        Ok(Test {
+            name: String::from("jack"),
+            percent: 100,
+            city: "Busan",
+        })
+    );
+
+    let t4 = Test::new()
+        .name_async(|| async { " Sam" })
+        .percent(50)
+        .build()
+        .await;
+    println!("{:?}", t4);
+    assert_eq!(
+        t4,
+        Ok(Test {
+            name: String::from("sam"),
+            percent: 50,
+            city: "Seoul",
+        })
+    );
+}
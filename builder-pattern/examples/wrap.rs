@@ -0,0 +1,59 @@
+use builder_pattern::Builder;
+use std::borrow::Cow;
+use std::fmt::Debug;
+use std::rc::Rc;
+use std::sync::Arc;
+
+trait Handler: Debug {
+    fn handle(&self, request: &str) -> String;
+}
+
+#[derive(Debug)]
+struct Echo;
+
+impl Handler for Echo {
+    fn handle(&self, request: &str) -> String {
+        request.to_string()
+    }
+}
+
+#[derive(Builder, Debug)]
+struct Route {
+    #[setter(value, lazy, wrap)]
+    pub handler: Arc<dyn Handler + Send + Sync>,
+    #[setter(wrap)]
+    pub data: Box<[u8]>,
+    #[into]
+    #[setter(wrap)]
+    pub name: Rc<String>,
+    #[default(Cow::Borrowed("/"))]
+    #[setter(wrap)]
+    pub path: Cow<'static, str>,
+    #[setter(strip_option, wrap)]
+    pub fallback: Option<Box<dyn Handler>>,
+}
+
+fn main() {
+    let r1 = Route::new()
+        .handler(Echo)
+        .data(vec![1, 2, 3])
+        .name("echo")
+        .build();
+    println!("{:?}", r1);
+    assert_eq!(r1.handler.handle("hello"), "hello");
+    assert_eq!(&*r1.data, &[1, 2, 3]);
+    assert_eq!(*r1.name, "echo");
+    assert_eq!(r1.path, "/");
+    assert!(r1.fallback.is_none());
+
+    let r2 = Route::new()
+        .handler_lazy(|| Echo)
+        .data(String::from("abc").into_bytes())
+        .name(String::from("echo"))
+        .path(String::from("/echo"))
+        .fallback(Echo)
+        .build();
+    println!("{:?}", r2);
+    assert_eq!(r2.path, "/echo");
+    assert_eq!(r2.fallback.unwrap().handle("bye"), "bye");
+}
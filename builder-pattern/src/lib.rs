@@ -78,9 +78,9 @@
 //! - **Chaining**: Can make structure with chained setters.
 //! - **Complex types are supported**: Lifetime, trait bounds, and where clauses are well supported.
 //! - **Type safety**: Autocompletion tools can suggest correct setters to build the struct. Also, `build`
-//! function is allowed only the all of required fields are provided. **No Result**, **No Unwrap**. Just use it.
+//!   function is allowed only the all of required fields are provided. **No Result**, **No Unwrap**. Just use it.
 //! - **Lazy evaluation and asynchronous**: Lazy evaluation and asynchronous are supported.
-//! The values will be evaluated when the structure is built.
+//!   The values will be evaluated when the structure is built.
 //! - **No additional tasks**: There's no additional constraints to use the macro. Any structures and fields are allowed.
 //! - **Auto-generated documentation**: Documentation for the builder functions are automatically generated.
 //!
@@ -125,10 +125,36 @@
 //! let t2 = Test::new().a(3).build();
 //! ```
 //!
+//! ### `#[default]`, `#[builder(default_all)]` and `#[required]`
+//!
+//! `#[default]` without an argument means `#[default(Default::default())]`. If the structure has
+//! `#[builder(default_all)]`, all fields are `Default::default()` by default unless they are
+//! `#[required]`. For fields depending on type parameters, bounds like `T: Default` are added to the
+//! builder.
+//!
+//! ```
+//! # use builder_pattern::Builder;
+//! #[derive(Builder)]
+//! #[builder(default_all)]
+//! struct Options<T> {
+//!     #[required]
+//!     pub name: String,
+//!     pub verbose: bool,
+//!     pub extra: T,
+//! }
+//!
+//! let options = Options::<Option<u8>>::new()  // OptionsBuilder<(), (), (), ...>
+//!     .name(String::from("app"))              // OptionsBuilder<String, (), (), ...>
+//!     .build();                               // Options<Option<u8>>
+//! assert!(!options.verbose);
+//! assert_eq!(options.extra, None);
+//! ```
+//!
 //! ### `#[hidden]`
 //!
 //! If this attribute is present, the builder function would not be generated for the field.
-//! This field requires `default` or `default_lazy` attribute.
+//! This field requires `default` or `default_lazy` attribute, or an implicit default value like
+//! `#[builder(default_all)]`.
 //!
 //! Example:
 //!
@@ -244,6 +270,140 @@
 //! # });
 //! ```
 //!
+//! ### `#[setter(strip_option)]`
+//!
+//! A setter for a field of `Option<T>` having this option accepts `T` and wraps it with `Some`.
+//! The field is `None` by default unless `#[default(expr)]` is given, and lazy and async setters
+//! follow the same rule. `#[builder(strip_option)]` on the structure applies it to all fields of
+//! `Option<T>`.
+//!
+//! ```
+//! # use builder_pattern::Builder;
+//! #[derive(Builder)]
+//! struct Test {
+//!     pub name: String,
+//!     #[into]
+//!     #[setter(value, lazy, strip_option)]
+//!     pub nickname: Option<String>,
+//!     #[setter(strip_option)]
+//!     pub age: Option<u8>,
+//! }
+//!
+//! let test = Test::new()          // TestBuilder<(), (), (), ...>
+//!     .name(String::from("Joe"))  // TestBuilder<String, (), (), ...>
+//!     .nickname("J")              // TestBuilder<String, (), Option<String>, ...>
+//!     .build();                   // Test
+//! assert_eq!(test.nickname, Some(String::from("J")));
+//! assert_eq!(test.age, None);
+//! ```
+//!
+//! ### `#[setter(each = "name")]`
+//!
+//! For a field of `Vec`, `VecDeque`, `HashSet`, `BTreeSet`, `HashMap` or `BTreeMap`, it provides
+//! a setter adding an item to the collection, and `extend_` setter adding items of an iterator.
+//! They can be called any number of times, and don't change the state of the builder. Items of
+//! maps are given as a key and a value. The field is empty by default unless `#[default(expr)]` is
+//! given. When a value of the field is set lazily or asynchronously, the items are added after the
+//! value is evaluated. The field has no value setter unless `value` is given explicitly. Value,
+//! lazy and asynchronous setters of the field set the whole collection replacing the items added
+//! before, so they should be called first. Validators and `#[required]` are not supported.
+//!
+//! ```
+//! # use builder_pattern::Builder;
+//! # use std::collections::HashMap;
+//! #[derive(Builder)]
+//! struct Command {
+//!     #[setter(value, each = "arg")]
+//!     pub args: Vec<String>,
+//!     #[setter(each = "env")]
+//!     pub envs: HashMap<String, String>,
+//! }
+//!
+//! let command = Command::new()                    // CommandBuilder<(), (), ...>
+//!     .arg(String::from("-l"))                    // CommandBuilder<(), (), ...>
+//!     .extend_args(vec![String::from("-a")])      // CommandBuilder<(), (), ...>
+//!     .env(String::from("LANG"), String::from("C"))
+//!     .build();                                   // Command
+//! assert_eq!(command.args, vec!["-l", "-a"]);
+//! assert_eq!(command.envs["LANG"], "C");
+//! ```
+//!
+//! ### `#[setter(flag)]`
+//!
+//! For a field of `bool`, it provides a setter taking no arguments, which sets the field to
+//! `true`, and `_if` setter taking a condition. The field is `false` by default unless
+//! `#[default(expr)]` is given. It can't be used with the value setter.
+//!
+//! ```
+//! # use builder_pattern::Builder;
+//! #[derive(Builder)]
+//! struct Options {
+//!     #[setter(flag)]
+//!     pub verbose: bool,
+//!     #[setter(flag)]
+//!     pub quiet: bool,
+//! }
+//!
+//! let options = Options::new()    // OptionsBuilder<(), (), ...>
+//!     .verbose()                  // OptionsBuilder<(), bool, ...>
+//!     .quiet_if(false)            // OptionsBuilder<bool, bool, ...>
+//!     .build();                   // Options
+//! assert!(options.verbose);
+//! assert!(!options.quiet);
+//! ```
+//!
+//! ### `#[setter(name = "name", with = closure)]`
+//!
+//! It provides an additional setter with the name, which takes arguments of the closure.
+//! The closure should have types of its arguments and return a value of the field's type.
+//! The attribute can be given multiple times, and transforms and validators of the field are
+//! applied to the value.
+//!
+//! ```
+//! # use builder_pattern::Builder;
+//! # use std::time::Duration;
+//! #[derive(Builder)]
+//! struct Test {
+//!     #[setter(name = "timeout_secs", with = |secs: u64| Duration::from_secs(secs))]
+//!     #[setter(name = "timeout_millis", with = |millis: u64| Duration::from_millis(millis))]
+//!     pub timeout: Duration,
+//! }
+//!
+//! let test = Test::new()          // TestBuilder<(), ...>
+//!     .timeout_secs(10)           // TestBuilder<Duration, ...>
+//!     .build();                   // Test
+//! assert_eq!(test.timeout, Duration::from_secs(10));
+//! ```
+//!
+//! ### `#[setter(overridable)]`
+//!
+//! Setters of a field having this option are provided even if the field is already set, so a
+//! value can be overridden. For fields having default values, `reset_` functions restoring the
+//! default values are provided as well. `#[builder(overridable)]` on the structure applies it to
+//! all fields.
+//!
+//! ```
+//! # use builder_pattern::Builder;
+//! #[derive(Builder)]
+//! struct Test {
+//!     #[setter(overridable)]
+//!     pub name: String,
+//!     #[default(8080)]
+//!     #[setter(overridable)]
+//!     pub port: u16,
+//! }
+//!
+//! let base = Test::new()          // TestBuilder<(), (), ...>
+//!     .name(String::from("base")) // TestBuilder<String, (), ...>
+//!     .port(3000);                // TestBuilder<String, u16, ...>
+//! let test = base
+//!     .name(String::from("Joe"))  // TestBuilder<String, u16, ...>
+//!     .reset_port()               // TestBuilder<String, (), ...>
+//!     .build();                   // Test
+//! assert_eq!(test.name, "Joe");
+//! assert_eq!(test.port, 8080);
+//! ```
+//!
 //! ### `#[into]`
 //!
 //! A setter function for a field having this attribute will accept `Into`
@@ -271,6 +431,114 @@
 //!     .build();                   // Test
 //! ```
 //!
+//! ### `#[setter(wrap)]`
+//!
+//! For a field of `Box<T>`, `Arc<T>`, `Rc<T>` or `Cow<'_, T>`, setters accept `T` and wrap it.
+//! If `T` is a trait object like `dyn Handler`, setters accept a value implementing the trait and
+//! coerce it. If `T` is unsized like `str` or `[u8]`, setters accept values which can be converted
+//! into the field, such as `String` or `Vec<u8>`.
+//!
+//! ```
+//! # use builder_pattern::Builder;
+//! # use std::sync::Arc;
+//! # use std::fmt::Display;
+//! #[derive(Builder)]
+//! struct Test {
+//!     #[setter(wrap)]
+//!     pub name: Arc<dyn Display + Send + Sync>,
+//!     #[setter(wrap)]
+//!     pub data: Box<[u8]>,
+//! }
+//!
+//! let test = Test::new()          // TestBuilder<(), (), ...>
+//!     .name("Joe")                // TestBuilder<(), Arc<dyn Display + Send + Sync>, ...>
+//!     .data(vec![1, 2, 3])        // TestBuilder<Box<[u8]>, Arc<dyn Display + Send + Sync>, ...>
+//!     .build();                   // Test
+//! assert_eq!(test.name.to_string(), "Joe");
+//! assert_eq!(&*test.data, &[1, 2, 3]);
+//! ```
+//!
+//! ### `#[setter(closure)]`
+//!
+//! For a field of a boxed function like `Box<dyn Fn(T) -> U>` or `Arc<dyn Fn(T) -> U>`, setters
+//! accept closures and box them. Types of arguments of the closures can be inferred.
+//!
+//! ```
+//! # use builder_pattern::Builder;
+//! #[derive(Builder)]
+//! struct Test {
+//!     #[setter(closure)]
+//!     pub handler: Box<dyn Fn(String) -> usize>,
+//! }
+//!
+//! let test = Test::new()          // TestBuilder<(), ...>
+//!     .handler(|s| s.len())       // TestBuilder<Box<dyn Fn(String) -> usize>, ...>
+//!     .build();                   // Test
+//! assert_eq!((test.handler)(String::from("Joe")), 3);
+//! ```
+//!
+//! ### `#[setter(try_into | parse)]`
+//!
+//! Setters for a field having `try_into` accept `TryInto` trait, and setters for a field having
+//! `parse` accept a string and parse it via `FromStr` trait. Value setters return `Result` with
+//! the error message of the failed conversion. For lazy and async setters, the conversion error
+//! is returned when the structure is built, naming the field and the type.
+//!
+//! ```
+//! # use builder_pattern::Builder;
+//! # use std::net::IpAddr;
+//! #[derive(Builder, Debug)]
+//! struct Server {
+//!     #[setter(value, lazy, parse)]
+//!     pub ip: IpAddr,
+//!     #[setter(try_into)]
+//!     pub port: u16,
+//! }
+//!
+//! let server = Server::new()      // ServerBuilder<(), (), ...>
+//!     .ip("127.0.0.1")            // Ok(ServerBuilder<IpAddr, (), ...>)
+//!     .unwrap()
+//!     .port(8080_i32)             // Ok(ServerBuilder<IpAddr, u16, ...>)
+//!     .unwrap()
+//!     .build();                   // Server
+//! assert_eq!(server.port, 8080);
+//!
+//! assert!(Server::new().port(-1).is_err());
+//! let server = Server::new()
+//!     .ip_lazy(|| "localhost")
+//!     .port(8080_i32)
+//!     .unwrap()
+//!     .build();                   // Result<Server, &'static str>
+//! assert_eq!(server.unwrap_err(), "Conversion of `ip` into `IpAddr` failed.");
+//! ```
+//!
+//! ### `#[transform(expr)]`
+//!
+//! Normalize a value before it is validated and stored. `expr` should be a function or a closure
+//! taking the field's type and returning the same type. The attribute can be given multiple times,
+//! and the transforms are applied in order for all kinds of setters. If `default` is given like
+//! `#[transform(expr, default)]`, it is also applied to the default value.
+//!
+//! ```
+//! # use builder_pattern::Builder;
+//! #[derive(Builder)]
+//! struct Test {
+//!     #[into]
+//!     #[transform(|s: String| s.trim().to_string())]
+//!     #[transform(|s: String| s.to_lowercase())]
+//!     pub name: String,
+//!     #[default(200)]
+//!     #[transform(|v: i32| v.min(100), default)]
+//!     pub percent: i32,
+//! }
+//!
+//! let test1 = Test::new()         // TestBuilder<(), (), ...>
+//!     .name("  Joe ")             // TestBuilder<String, (), ...>
+//!     .build();                   // Test
+//! assert_eq!(test1.name, "joe");
+//! assert_eq!(test1.percent, 100);
+//! ```
+//!
 //! ### `#[validator(expr)]`
 //!
 //! Implement a validator for a field. `expr` could be a validating function that takes the field's
@@ -358,6 +626,578 @@
 //! # });
 //! ```
 //!
+//! A validator may also take a reference of the value with `ref = expr`. `expr` should return
+//! `Result<(), E>`, or `bool` if `message` is given. The message is used as the error when the
+//! predicate returns `false`. It makes the validator reusable for other code.
+//!
+//! ```
+//! # use builder_pattern::Builder;
+//! #[derive(Builder)]
+//! struct Test {
+//!     #[validator(ref = is_not_empty)]
+//!     #[into]
+//!     pub name: String,
+//!     #[validator(ref = is_positive, message = "Age should be positive.")]
+//!     pub age: i32,
+//! }
+//!
+//! fn is_not_empty(name: &str) -> Result<(), &'static str> {
+//!     if name.is_empty() {
+//!         Err("Name cannot be empty.")
+//!     } else {
+//!         Ok(())
+//!     }
+//! }
+//!
+//! fn is_positive(v: &i32) -> bool {
+//!     *v > 0
+//! }
+//!
+//! let test1 = Test::new()          // TestBuilder<(), (), ...>
+//!     .name("Hello")               // Ok(TestBuilder<String, (), ...>)
+//!     .unwrap()                    // TestBuilder<String, (), ...>
+//!     .age(-3);                    // Err(String{ "Validation failed: \"Age should be positive.\"" })
+//! assert!(test1.is_err());
+//! ```
+//!
+//! Built-in validators in [`validators`] can be used with the `builtin::` prefix:
+//! `builtin::non_empty`, `builtin::len(min, max)`, `builtin::range(a..=b)`,
+//! `builtin::one_of([...])`, `builtin::url`, `builtin::email`, `builtin::path_exists` and
+//! `builtin::matches(regex)` (requires the crate feature `regex`; the pattern is compiled once for
+//! each validator, and an invalid pattern fails the validation). They can be composed with
+//! `builtin::all(...)` and `builtin::any(...)`. `any` requires the field to implement `Clone`.
+//! Validators without the prefix, or with names which are not built-in, are always your own
+//! functions. The built-in validators return [`validators::ValidationError`] naming the failed
+//! rule. For lazy and asynchronous setters, it is converted into its message, which starts with the
+//! name of the rule.
+//!
+//! ```
+//! # use builder_pattern::Builder;
+//! #[derive(Builder)]
+//! struct Test {
+//!     #[validator(builtin::all(builtin::non_empty, builtin::len(max = 8)))]
+//!     #[into]
+//!     pub name: String,
+//!     #[validator(builtin::range(0..=150))]
+//!     pub age: u8,
+//!     #[validator(builtin::any(builtin::email, builtin::url))]
+//!     pub contact: &'static str,
+//! }
+//!
+//! let test1 = Test::new()         // TestBuilder<(), (), (), ...>
+//!     .name("Lorem ipsum");       // Err(String{ "Validation failed: ValidationError { rule: \"len\", .. }" })
+//! assert!(test1.is_err());
+//!
+//! let test2 = Test::new()         // TestBuilder<(), (), (), ...>
+//!     .contact("joe@example.com") // Ok(TestBuilder<(), (), &'static str, ...>)
+//!     .unwrap()                   // TestBuilder<(), (), &'static str, ...>
+//!     .age(200);                  // Err(String{ "Validation failed: ValidationError { rule: \"range\", .. }" })
+//! assert!(test2.is_err());
+//! ```
+//!
+//! Small invariants don't need a separate function. A closure can be used as a validator, and
+//! `check = expr` validates the value with a `bool` expression in which the value is bound to `v`.
+//! `message` is used as the error when the expression is `false`.
+//!
+//! ```
+//! # use builder_pattern::Builder;
+//! #[derive(Builder)]
+//! struct Test {
+//!     #[validator(|v: i32| if v > 0 { Ok(v) } else { Err("negative") })]
+//!     pub positive: i32,
+//!     #[validator(check = v % 2 == 0, message = "must be even")]
+//!     pub even: i32,
+//! }
+//!
+//! let test1 = Test::new()         // TestBuilder<(), (), ...>
+//!     .positive(3)                // Ok(TestBuilder<i32, (), ...>)
+//!     .unwrap()                   // TestBuilder<i32, (), ...>
+//!     .even(3);                   // Err(String{ "Validation failed: \"must be even\"" })
+//! assert!(test1.is_err());
+//! ```
+//!
+//! ### `#[validator_async(expr)]`
+//!
+//! Implement an asynchronous validator for a field. `expr` could be a function that takes the field's
+//! type and returns a `Future` of `Result`. The validator is evaluated when the structure is built,
+//! so all setters of the field make the builder asynchronous. If `validator` is also given,
+//! it is evaluated before the asynchronous one.
+//!
+//! ```
+//! # use builder_pattern::Builder;
+//! #[derive(Builder)]
+//! struct Test {
+//!     #[validator_async(is_available)]
+//!     pub port: u16,
+//! }
+//!
+//! async fn is_available(port: u16) -> Result<u16, &'static str> {
+//!     if port != 8080 {
+//!         Ok(port)
+//!     } else {
+//!         Err("The port is already in use.")
+//!     }
+//! }
+//!
+//! # tokio_test::block_on(async {
+//! let test1 = Test::new()         // TestBuilder<(), ...>
+//!     .port(3000)                 // TestBuilder<u16, ...>
+//!     .build()                    // Future<Result<Test, &'static str>>
+//!     .await;                     // Ok(Test)
+//! assert!(test1.is_ok());
+//!
+//! let test2 = Test::new()         // TestBuilder<(), ...>
+//!     .port(8080)                 // TestBuilder<u16, ...>
+//!     .build()                    // Future<Result<Test, &'static str>>
+//!     .await;                     // Err("The port is already in use.")
+//! assert!(test2.is_err());
+//! # });
+//! ```
+//!
+//! ### Conditional setters
+//!
+//! For fields having default values, `maybe_` setters taking `Option` are provided as well.
+//! They change the state of the builder like the other setters, but the field keeps its default
+//! value if `None` is given. `maybe_*_lazy` and `maybe_*_async` setters are provided for lazy and
+//! async setters. If a name of the setters collides with another function of the builder, like a
+//! setter of a field named `maybe_port`, the setter is not provided. Rename the field via
+//! `#[setter(rename = "...")]` to get it.
+//!
+//! ```
+//! # use builder_pattern::Builder;
+//! #[derive(Builder)]
+//! struct Test {
+//!     #[default(8080)]
+//!     pub port: u16,
+//!     #[default(String::from("localhost"))]
+//!     pub host: String,
+//! }
+//!
+//! let port: Option<u16> = Some(3000);
+//! let host: Option<String> = None;
+//! let test = Test::new()          // TestBuilder<(), (), ...>
+//!     .maybe_port(port)           // TestBuilder<(), u16, ...>
+//!     .maybe_host(host)           // TestBuilder<String, u16, ...>
+//!     .build();                   // Test
+//! assert_eq!(test.port, 3000);
+//! assert_eq!(test.host, "localhost");
+//! ```
+//!
+//! ### Getters
+//!
+//! `#[builder(getters)]` for all fields or `#[setter(getter)]` for a field provides `is_*_set` to
+//! check whether the field is set, and `*_ref` to peek the value of the field which is set. `*_ref`
+//! returns `None` if the value is lazy or async, because it is not evaluated yet. `is_*_set` is a
+//! `const fn` provided in every state of the builder. In code generic over the state, it requires
+//! the state to implement [`FieldState<S, N>`](FieldState), where `S` is the
+//! structure and `N` is the index of the field in the type parameters of the builder. For fields
+//! whose types depend on type parameters, the set state is represented as
+//! [`Set<T>`](Set) instead of `T`, since `T` may be `()`.
+//!
+//! ```
+//! # use builder_pattern::{Builder, FieldState};
+//! #[derive(Builder)]
+//! #[builder(getters)]
+//! struct User {
+//!     #[setter(value, lazy)]
+//!     pub name: String,
+//!     pub email: String,
+//! }
+//!
+//! let builder = User::new().name(String::from("Joe"));
+//! assert!(builder.is_name_set());
+//! assert!(!builder.is_email_set());
+//!
+//! let name = builder.name_ref().unwrap().to_lowercase();
+//! let user = builder.email(format!("{}@example.com", name)).build();
+//! assert_eq!(user.email, "joe@example.com");
+//!
+//! // `email` is the first field of the builder.
+//! fn has_email<E, N, M, V>(builder: &UserBuilder<'_, E, N, M, V>) -> bool
+//! where
+//!     E: FieldState<User, 0>,
+//! {
+//!     builder.is_email_set()
+//! }
+//! assert!(!has_email(&User::new()));
+//! ```
+//!
+//! ### Transforming values
+//!
+//! `#[builder(maps)]` for all fields or `#[setter(map)]` for a field provides `map_*` functions,
+//! which transform the value of the field which is set. For lazy and async values, the function is
+//! applied after they are evaluated. The validators of the field are applied to the
+//! transformed value as well. If the field has only value setters, it is validated immediately and
+//! `Result` is returned. Otherwise, it is validated when the structure is built. If such a field
+//! has an asynchronous validator as well, `map_*` is an `async fn`, which awaits the value to
+//! transform it. Like the other functions, `map_*` is rejected if its name collides with another
+//! function of the builder.
+//!
+//! ```
+//! # use builder_pattern::Builder;
+//! #[derive(Builder)]
+//! struct Server {
+//!     #[setter(value, lazy, map)]
+//!     pub name: String,
+//!     #[validator(is_not_privileged)]
+//!     #[setter(map)]
+//!     pub port: u16,
+//! }
+//!
+//! fn is_not_privileged(port: u16) -> Result<u16, &'static str> {
+//!     if port >= 1024 {
+//!         Ok(port)
+//!     } else {
+//!         Err("The port is privileged.")
+//!     }
+//! }
+//!
+//! let server = Server::new()
+//!     .name_lazy(|| String::from("api"))
+//!     .map_name(|name| name.to_uppercase())
+//!     .port(8080)
+//!     .unwrap()
+//!     .map_port(|port| port + 1)
+//!     .unwrap()
+//!     .build();
+//! assert_eq!(server.name, "API");
+//! assert_eq!(server.port, 8081);
+//! ```
+//!
+//! ### Naming
+//!
+//! Names of the builder, the constructor and setters can be changed via `#[builder(...)]`.
+//! `name` is the name of the builder, and `constructor` is the name of the function creating it,
+//! which is `new` by default. `prefix` is prepended to names of setters, and `lazy_suffix` and
+//! `async_suffix` replace `_lazy` and `_async`. A field can be renamed in all of its functions via
+//! `#[setter(rename = "...")]`. For raw identifiers like `r#type`, names are made without `r#`.
+//!
+//! ```
+//! # use builder_pattern::Builder;
+//! #[derive(Builder)]
+//! #[builder(name = "RequestConfig", constructor = "builder", prefix = "with_", lazy_suffix = "_with")]
+//! struct Request {
+//!     #[setter(value, lazy)]
+//!     pub r#type: String,
+//!     #[setter(rename = "address")]
+//!     pub url: String,
+//! }
+//!
+//! impl Request {
+//!     pub fn new(url: &str) -> Self {
+//!         Request {
+//!             r#type: String::from("GET"),
+//!             url: String::from(url),
+//!         }
+//!     }
+//! }
+//!
+//! let config: RequestConfig<_, _, _, _> = Request::builder()
+//!     .with_type_with(|| String::from("POST"))
+//!     .with_address(String::from("localhost"));
+//! let request = config.build();
+//! assert_eq!(request.r#type, "POST");
+//! assert_eq!(request.url, "localhost");
+//! ```
+//!
+//! ### Visibility
+//!
+//! The builder and the constructor have the visibility of the structure, and setters have the
+//! visibility of their fields. They can be changed with any visibility via `#[builder(vis = "...")]`
+//! and `#[setter(vis = "...")]`. The visibility of the build function is the same as the builder,
+//! and it can be changed via `#[builder(build_fn(vis = "..."))]`. `#[setter(vis = "...")]` can be
+//! given as a separate attribute, and it keeps the kinds of setters given by the other attributes.
+//!
+//! ```
+//! mod config {
+//! #   use builder_pattern::Builder;
+//!     #[derive(Builder)]
+//!     #[builder(vis = "pub(crate)")]
+//!     pub struct Config {
+//!         pub name: String,
+//!         #[default(false)]
+//!         #[setter(vis = "pub(super)")]
+//!         pub debug: bool,
+//!     }
+//! }
+//! use config::Config;
+//!
+//! let config = Config::new()
+//!     .name(String::from("app"))
+//!     .debug(true)
+//!     .build();
+//! assert!(config.debug);
+//! # // cont
+//! ```
+//! ```compile_fail
+//! # //cont
+//! # mod outer {
+//! # pub mod config {
+//! #     use builder_pattern::Builder;
+//! #     #[derive(Builder)]
+//! #     #[builder(vis = "pub(crate)")]
+//! #     pub struct Config {
+//! #         pub name: String,
+//! #         #[default(false)]
+//! #         #[setter(vis = "pub(super)")]
+//! #         pub debug: bool,
+//! #     }
+//! # }
+//! # }
+//! # use outer::config::Config;
+//! // `debug` is not visible outside of the parent module.
+//! let config = Config::new()
+//!     .name(String::from("app"))
+//!     .debug(true)
+//!     .build();
+//! ```
+//!
+//! ### `#[builder(validate = expr, finalize = expr)]`
+//!
+//! Struct-level hooks which are called with the assembled structure at the end of `build`.
+//! `finalize` takes the structure and returns a transformed one, and `validate` takes the
+//! structure and returns `Result<Self, E>`. It makes invariants across fields possible.
+//! `finalize` is applied before `validate`. If `validate` is given, `build` always returns `Result`.
+//!
+//! ```
+//! # use builder_pattern::Builder;
+//! #[derive(Builder, Debug)]
+//! #[builder(validate = Range::check)]
+//! struct Range {
+//!     min: i32,
+//!     max: i32,
+//! }
+//!
+//! impl Range {
+//!     fn check(self) -> Result<Self, &'static str> {
+//!         if self.min <= self.max {
+//!             Ok(self)
+//!         } else {
+//!             Err("`min` is greater than `max`.")
+//!         }
+//!     }
+//! }
+//!
+//! let r1 = Range::new().min(1).max(3).build(); // Ok(Range)
+//! assert!(r1.is_ok());
+//! let r2 = Range::new().min(3).max(1).build(); // Err("`min` is greater than `max`.")
+//! assert!(r2.is_err());
+//! ```
+//!
+//! The error type of `build` is `&'static str` unless it is given by `#[builder(error = Type)]`.
+//! Errors of `validate` and field validators are converted into it via `Into`, so a hook returning
+//! `Result<Self, String>` requires `error = String`. `validate(ref = expr)` takes a reference of the
+//! structure and returns `Result<(), E>` instead.
+//!
+//! ```
+//! # use builder_pattern::Builder;
+//! #[derive(Builder, Debug)]
+//! #[builder(validate(ref = Range::check), error = String)]
+//! struct Range {
+//!     min: i32,
+//!     max: i32,
+//! }
+//!
+//! impl Range {
+//!     fn check(&self) -> Result<(), String> {
+//!         if self.min <= self.max {
+//!             Ok(())
+//!         } else {
+//!             Err(format!("`min`({}) is greater than `max`({}).", self.min, self.max))
+//!         }
+//!     }
+//! }
+//!
+//! let r = Range::new().min(3).max(1).build();
+//! assert_eq!(r.unwrap_err(), "`min`(3) is greater than `max`(1).");
+//! ```
+//!
+//! ### `#[builder(context = Type)]`
+//!
+//! Validators and lazy defaults may need runtime context, like an allow-list or a configuration.
+//! If `context` is given, the builder provides `build_with(&ctx)` instead of `build`. A validator
+//! declared as `#[validator(expr, with_context)]` takes the value and `&Type`, and it is evaluated
+//! when the structure is built. A lazy default can take the context as well if it is a closure
+//! having an argument or declared as `#[default_lazy(expr, with_context)]`.
+//!
+//! ```
+//! # use builder_pattern::Builder;
+//! struct Context {
+//!     allowed_users: Vec<&'static str>,
+//!     root: &'static str,
+//! }
+//!
+//! #[derive(Builder)]
+//! #[builder(context = Context)]
+//! struct Test {
+//!     #[validator(is_allowed, with_context)]
+//!     pub user: &'static str,
+//!     #[default_lazy(|ctx: &Context| format!("{}/home", ctx.root))]
+//!     pub home: String,
+//! }
+//!
+//! fn is_allowed(user: &'static str, ctx: &Context) -> Result<&'static str, &'static str> {
+//!     if ctx.allowed_users.contains(&user) {
+//!         Ok(user)
+//!     } else {
+//!         Err("The user is not allowed.")
+//!     }
+//! }
+//!
+//! let ctx = Context {
+//!     allowed_users: vec!["joe"],
+//!     root: "/root",
+//! };
+//! let test1 = Test::new()         // TestBuilder<(), (), ...>
+//!     .user("joe")                // TestBuilder<&'static str, (), ...>
+//!     .build_with(&ctx)           // Ok(Test)
+//!     .unwrap();                  // Test
+//! assert_eq!(test1.home, "/root/home");
+//!
+//! let test2 = Test::new()         // TestBuilder<(), (), ...>
+//!     .user("jack")               // TestBuilder<&'static str, (), ...>
+//!     .build_with(&ctx);          // Err("The user is not allowed.")
+//! assert!(test2.is_err());
+//! ```
+//!
+//! ### `#[builder(build_fn(name = "name", skip))]`
+//!
+//! The build function can be renamed via `name`. If `skip` is given, the builder provides
+//! `into_parts` instead, which returns a `XParts` structure having the resolved fields. It makes it
+//! possible to write the build function by hand, with custom error handling or logging. Type
+//! parameters of the builder are the types of required fields sorted by their names, and then
+//! optional fields. `validate` and `finalize` can't be used with `skip`.
+//!
+//! ```
+//! # use builder_pattern::Builder;
+//! #[derive(Builder, Debug)]
+//! #[builder(build_fn(skip))]
+//! struct Person {
+//!     pub name: String,
+//!     pub age: u8,
+//! }
+//!
+//! impl<'a> PersonBuilder<'a, u8, String, (), ()> {
+//!     fn build(self) -> Result<Person, String> {
+//!         let PersonParts { name, age } = self.into_parts();
+//!         if age < 14 {
+//!             return Err(format!("{} is too young.", name));
+//!         }
+//!         Ok(Person { name, age })
+//!     }
+//! }
+//!
+//! let person = Person::new()
+//!     .name(String::from("Joe"))
+//!     .age(10)
+//!     .build();
+//! assert_eq!(person.unwrap_err(), "Joe is too young.");
+//! ```
+//!
+//! ### `#[builder(constructor(name = "name", args(a, b, ...)))]`
+//!
+//! Additional constructors can be given, which create the builder with the fields set by their
+//! arguments. Arguments follow the rules of value setters of each field, like `#[into]` and
+//! validators. `#[builder(constructor = "name")]` renames `new` instead.
+//!
+//! ```
+//! # use builder_pattern::Builder;
+//! #[derive(Builder)]
+//! #[builder(constructor(name = "with_url", args(url)))]
+//! #[builder(constructor(name = "with_host_port", args(host, port)))]
+//! struct Connection {
+//!     #[default(None)]
+//!     pub url: Option<String>,
+//!     #[default(String::from("localhost"))]
+//!     pub host: String,
+//!     #[default(80)]
+//!     pub port: u16,
+//!     pub timeout: u32,
+//! }
+//!
+//! let c1 = Connection::with_url(Some(String::from("https://example.com")))
+//!     .timeout(10)
+//!     .build();
+//! assert_eq!(c1.host, "localhost");
+//!
+//! let c2 = Connection::with_host_port(String::from("example.com"), 8080)
+//!     .timeout(10)
+//!     .build();
+//! assert_eq!(c2.port, 8080);
+//! ```
+//!
+//! ### `#[builder(preset(name(field = expr, ...), ...))]`
+//!
+//! Presets create the builder with the fields set by the given values, like `Config::dev()`. Each
+//! preset is given as its name followed by the values of the fields, which follow the rules of value
+//! setters of each field. The fields set by a preset are in the state of [`Preset<T>`](Preset), so
+//! they can be overridden by their setters, and the structure can be built like they are set.
+//! Getters and `map_*` functions of the fields are provided in the state as well. Builders created
+//! by the other constructors are not affected, so their fields are still set at most once.
+//!
+//! ```
+//! # use builder_pattern::Builder;
+//! #[derive(Builder)]
+//! #[builder(preset(dev(host = "localhost", port = 8080, debug = true), prod(port = 443)))]
+//! struct Config {
+//!     #[into]
+//!     pub host: String,
+//!     pub port: u16,
+//!     #[default(false)]
+//!     pub debug: bool,
+//! }
+//!
+//! let config = Config::dev()      // ConfigBuilder<Preset<String>, Preset<u16>, Preset<bool>, ...>
+//!     .port(3000)                 // ConfigBuilder<Preset<String>, u16, Preset<bool>, ...>
+//!     .build();                   // Config
+//! assert_eq!(config.host, "localhost");
+//! assert_eq!(config.port, 3000);
+//! assert!(config.debug);
+//! # // cont
+//! ```
+//! ```compile_fail
+//! # // cont
+//! # use builder_pattern::Builder;
+//! # #[derive(Builder)]
+//! # #[builder(preset(dev(host = "localhost", port = 8080, debug = true), prod(port = 443)))]
+//! # struct Config {
+//! #     #[into]
+//! #     pub host: String,
+//! #     pub port: u16,
+//! #     #[default(false)]
+//! #     pub debug: bool,
+//! # }
+//! let config = Config::new()      // ConfigBuilder<(), (), (), ...>
+//!     .host("localhost")          // ConfigBuilder<String, (), (), ...>
+//!     .port(3000)                 // ConfigBuilder<String, u16, (), ...>
+//!     // Error: `port` is already set.
+//!     .port(3001)
+//!     .build();
+//! ```
+//!
+//! ### `#[builder(setter(name = "name", fields(a, b, ...)))]`
+//!
+//! It provides a setter setting several fields at once, which takes values of the fields in
+//! order. Arguments follow the rules of value setters of each field, like `#[into]` and
+//! validators. If `replace` is given, setters of the fields are not provided. The setter has
+//! the same visibility as the builder, which can be changed via `#[builder(vis = "...")]`.
+//!
+//! ```
+//! # use builder_pattern::Builder;
+//! #[derive(Builder)]
+//! #[builder(setter(name = "size", fields(width, height), replace))]
+//! struct Window {
+//!     pub width: u32,
+//!     pub height: u32,
+//! }
+//!
+//! let window = Window::new()      // WindowBuilder<(), (), ...>
+//!     .size(640, 480)             // WindowBuilder<u32, u32, ...>
+//!     .build();                   // Window
+//! assert_eq!(window.width, 640);
+//! assert_eq!(window.height, 480);
+//! ```
+//!
 //! ## Auto-Generated Documentation
 //!
 //! This crate generates documentation for the builder functions. If you document fields,
@@ -645,6 +1485,8 @@
 //! ```
 
 pub use builder_pattern_macro::Builder;
+pub use setter::{FieldState, Preset, Set};
 
 #[doc(hidden)]
 pub mod setter;
+pub mod validators;
@@ -1,3 +1,4 @@
+use core::marker::PhantomData;
 #[cfg(feature = "future")]
 use futures::future::LocalBoxFuture;
 
@@ -11,6 +12,118 @@ pub enum Setter<'a, T> {
     AsyncValidated(Box<dyn 'a + FnOnce() -> LocalBoxFuture<'a, Result<T, &'static str>>>),
 }
 
+impl<'a, T: 'a> Setter<'a, T> {
+    /// Apply a function to the value.
+    /// For lazy and asynchronous setters, it is applied after the value is resolved.
+    pub fn map<F: 'a + FnOnce(T) -> T>(self, f: F) -> Self {
+        match self {
+            Setter::Value(v) => Setter::Value(f(v)),
+            Setter::Lazy(g) => Setter::Lazy(Box::new(move || f(g()))),
+            Setter::LazyValidated(g) => Setter::LazyValidated(Box::new(move || g().map(f))),
+            #[cfg(feature = "future")]
+            Setter::Async(g) => {
+                Setter::Async(Box::new(move || Box::pin(async move { f(g().await) })))
+            }
+            #[cfg(feature = "future")]
+            Setter::AsyncValidated(g) => {
+                Setter::AsyncValidated(Box::new(move || Box::pin(async move { g().await.map(f) })))
+            }
+        }
+    }
+
+    /// Apply a function to the value, and validate the result.
+    /// The validation is deferred until the value is resolved.
+    pub fn map_validated<F, V>(self, f: F, validate: V) -> Self
+    where
+        F: 'a + FnOnce(T) -> T,
+        V: 'a + FnOnce(T) -> Result<T, &'static str>,
+    {
+        match self {
+            Setter::Value(v) => Setter::LazyValidated(Box::new(move || validate(f(v)))),
+            Setter::Lazy(g) => Setter::LazyValidated(Box::new(move || validate(f(g())))),
+            Setter::LazyValidated(g) => {
+                Setter::LazyValidated(Box::new(move || g().map(f).and_then(validate)))
+            }
+            #[cfg(feature = "future")]
+            Setter::Async(g) => Setter::AsyncValidated(Box::new(move || {
+                Box::pin(async move { validate(f(g().await)) })
+            })),
+            #[cfg(feature = "future")]
+            Setter::AsyncValidated(g) => Setter::AsyncValidated(Box::new(move || {
+                Box::pin(async move { g().await.map(f).and_then(validate) })
+            })),
+        }
+    }
+
+    /// Apply a function to the value, and validate the result asynchronously.
+    #[cfg(feature = "future")]
+    pub fn map_async_validated<F, V, Fut>(self, f: F, validate: V) -> Self
+    where
+        F: 'a + FnOnce(T) -> T,
+        V: 'a + FnOnce(T) -> Fut,
+        Fut: 'a + core::future::Future<Output = Result<T, &'static str>>,
+    {
+        let value: Box<dyn 'a + FnOnce() -> LocalBoxFuture<'a, Result<T, &'static str>>> =
+            match self {
+                Setter::Value(v) => Box::new(move || Box::pin(async move { Ok(v) })),
+                Setter::Lazy(g) => Box::new(move || Box::pin(async move { Ok(g()) })),
+                Setter::LazyValidated(g) => Box::new(move || Box::pin(async move { g() })),
+                Setter::Async(g) => Box::new(move || Box::pin(async move { Ok(g().await) })),
+                Setter::AsyncValidated(g) => g,
+            };
+        Setter::AsyncValidated(Box::new(move || {
+            Box::pin(async move {
+                match value().await {
+                    Ok(v) => validate(f(v)).await,
+                    Err(e) => Err(e),
+                }
+            })
+        }))
+    }
+}
+
 pub struct AsyncBuilderMarker {}
 
 pub struct HavingLazyValidator {}
+
+/// The state of a field which is set, used for fields having getters or set by presets whose types
+/// depend on type parameters. It can't be distinguished from `()` if the type of the field itself
+/// is used.
+pub struct Set<T>(PhantomData<T>);
+
+/// The state of a field set by a preset. The field can be overridden by its setters like `()`,
+/// and the structure can be built like the field is set.
+pub struct Preset<T>(PhantomData<T>);
+
+/// States in which setters of fields set by presets are provided, which are `()` and `Preset<T>`.
+pub trait Unset {}
+
+impl Unset for () {}
+
+impl<T> Unset for Preset<T> {}
+
+/// States of the `N`th field of the builder of `S` in which the structure can be built.
+/// It is used for required fields set by presets, whose states may be `Preset<T>`.
+pub trait Filled<S, const N: usize> {}
+
+impl<S, T, const N: usize> Filled<S, N> for Set<T> {}
+
+impl<S, T, const N: usize> Filled<S, N> for Preset<T> {}
+
+/// States of the `N`th field of the builder of `S`.
+/// It is implemented for `()` meaning the field is not set, and for the state of the set field.
+pub trait FieldState<S, const N: usize> {
+    const IS_SET: bool;
+}
+
+impl<S, const N: usize> FieldState<S, N> for () {
+    const IS_SET: bool = false;
+}
+
+impl<S, T, const N: usize> FieldState<S, N> for Set<T> {
+    const IS_SET: bool = true;
+}
+
+impl<S, T, const N: usize> FieldState<S, N> for Preset<T> {
+    const IS_SET: bool = true;
+}
@@ -0,0 +1,264 @@
+//! Built-in validators which can be used with `#[validator(builtin::name)]`.
+//!
+//! The validators return `Result<T, ValidationError>`, and the error names the failed rule.
+//! For lazy and asynchronous setters, the error is converted into its message, which is
+//! `&'static str` starting with the name of the rule.
+
+use core::fmt;
+use core::ops::RangeBounds;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::path::Path;
+#[cfg(feature = "regex")]
+use std::sync::OnceLock;
+
+/// An error of the built-in validators.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ValidationError {
+    /// Name of the failed rule, like `email`.
+    pub rule: &'static str,
+    /// Description of the error, which starts with the name of the rule.
+    pub message: &'static str,
+}
+
+/// Make a `ValidationError` of the rule.
+macro_rules! error {
+    ($rule:literal, $message:literal) => {
+        ValidationError {
+            rule: $rule,
+            message: concat!($rule, ": ", $message),
+        }
+    };
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message)
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+impl From<ValidationError> for &'static str {
+    fn from(e: ValidationError) -> Self {
+        e.message
+    }
+}
+
+/// Errors of other validators combined with built-in validators are named `custom`.
+impl From<&'static str> for ValidationError {
+    fn from(message: &'static str) -> Self {
+        ValidationError {
+            rule: "custom",
+            message,
+        }
+    }
+}
+
+/// Types having a length, which can be checked by [`non_empty`] and [`len`].
+/// The length of strings is counted by characters.
+pub trait Length {
+    fn length(&self) -> usize;
+}
+
+impl Length for str {
+    fn length(&self) -> usize {
+        self.chars().count()
+    }
+}
+
+impl Length for String {
+    fn length(&self) -> usize {
+        self.as_str().length()
+    }
+}
+
+impl<T> Length for [T] {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> Length for Vec<T> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> Length for VecDeque<T> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T, S> Length for HashSet<T, S> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T> Length for BTreeSet<T> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<K, V, S> Length for HashMap<K, V, S> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<K, V> Length for BTreeMap<K, V> {
+    fn length(&self) -> usize {
+        self.len()
+    }
+}
+
+impl<T: Length + ?Sized> Length for &T {
+    fn length(&self) -> usize {
+        (**self).length()
+    }
+}
+
+/// Checks the value is not empty.
+pub fn non_empty<T: Length>(value: T) -> Result<T, ValidationError> {
+    if value.length() == 0 {
+        Err(error!("non_empty", "the value is empty."))
+    } else {
+        Ok(value)
+    }
+}
+
+/// Checks the length of the value is in `min..=max`.
+///
+/// In the attribute, the bounds can be given by names like `len(max = 64)`.
+pub fn len<T: Length>(min: usize, max: usize) -> impl Fn(T) -> Result<T, ValidationError> {
+    move |value| {
+        let length = value.length();
+        if length < min {
+            Err(error!("len", "the value is too short."))
+        } else if length > max {
+            Err(error!("len", "the value is too long."))
+        } else {
+            Ok(value)
+        }
+    }
+}
+
+/// Checks the value is in the range.
+pub fn range<T, R>(range: R) -> impl Fn(T) -> Result<T, ValidationError>
+where
+    T: PartialOrd,
+    R: RangeBounds<T>,
+{
+    move |value| {
+        if range.contains(&value) {
+            Ok(value)
+        } else {
+            Err(error!("range", "the value is out of range."))
+        }
+    }
+}
+
+/// Checks the value is one of the candidates.
+pub fn one_of<T, U, L>(candidates: L) -> impl Fn(T) -> Result<T, ValidationError>
+where
+    T: PartialEq<U>,
+    L: AsRef<[U]>,
+{
+    move |value| {
+        if candidates.as_ref().iter().any(|c| value.eq(c)) {
+            Ok(value)
+        } else {
+            Err(error!("one_of", "the value is not one of the candidates."))
+        }
+    }
+}
+
+/// A regular expression which is compiled when it is used first.
+/// `builtin::matches(pattern)` makes it once for each use site, so `pattern` should be a constant.
+///
+/// It requires the crate feature `regex`.
+#[cfg(feature = "regex")]
+pub struct Pattern {
+    pattern: &'static str,
+    regex: OnceLock<Option<regex::Regex>>,
+}
+
+#[cfg(feature = "regex")]
+impl Pattern {
+    pub const fn new(pattern: &'static str) -> Self {
+        Pattern {
+            pattern,
+            regex: OnceLock::new(),
+        }
+    }
+
+    /// Checks the value matches the regular expression.
+    /// It fails if the pattern is not a valid regular expression.
+    pub fn validate<T: AsRef<str>>(&self, value: T) -> Result<T, ValidationError> {
+        match self
+            .regex
+            .get_or_init(|| regex::Regex::new(self.pattern).ok())
+        {
+            Some(regex) if regex.is_match(value.as_ref()) => Ok(value),
+            Some(_) => Err(error!("matches", "the value does not match the pattern.")),
+            None => Err(error!(
+                "matches",
+                "the pattern is not a valid regular expression."
+            )),
+        }
+    }
+}
+
+/// Checks the value looks like a URL, which is `scheme://rest`.
+pub fn url<T: AsRef<str>>(value: T) -> Result<T, ValidationError> {
+    let s = value.as_ref();
+    let valid = match s.find("://") {
+        Some(i) => {
+            let (scheme, rest) = (&s[..i], &s[i + 3..]);
+            scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.')
+                && !rest.is_empty()
+                && !s.contains(char::is_whitespace)
+        }
+        None => false,
+    };
+    if valid {
+        Ok(value)
+    } else {
+        Err(error!("url", "the value is not a valid URL."))
+    }
+}
+
+/// Checks the value looks like an email address, which is `local@domain.tld`.
+pub fn email<T: AsRef<str>>(value: T) -> Result<T, ValidationError> {
+    let s = value.as_ref();
+    let valid = match s.split_once('@') {
+        Some((local, domain)) => {
+            !local.is_empty()
+                && !domain.contains('@')
+                && domain.contains('.')
+                && !domain.starts_with('.')
+                && !domain.ends_with('.')
+                && !s.contains(char::is_whitespace)
+        }
+        None => false,
+    };
+    if valid {
+        Ok(value)
+    } else {
+        Err(error!("email", "the value is not a valid email address."))
+    }
+}
+
+/// Checks the path exists.
+pub fn path_exists<T: AsRef<Path>>(value: T) -> Result<T, ValidationError> {
+    if value.as_ref().exists() {
+        Ok(value)
+    } else {
+        Err(error!("path_exists", "the path does not exist."))
+    }
+}